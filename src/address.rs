@@ -57,6 +57,9 @@ impl<'de> Deserialize<'de> for Address {
     }
 }
 
+/// Appended to the account prefix of a chain to form the validator operator prefix
+const VALOPER_SUFFIX: &str = "valoper";
+
 /// An address that's derived from a given PublicKey, has the typical 20 bytes of data
 #[derive(PartialEq, Eq, Copy, Clone, Hash, Deserialize, Serialize)]
 pub struct BaseAddress {
@@ -135,15 +138,65 @@ impl Address {
     pub fn change_prefix<T: Into<String>>(&mut self, prefix: T) -> Result<(), AddressError> {
         match self {
             Address::Base(base_address) => {
-                base_address.prefix = ArrayString::new(&prefix.into())?;
+                base_address.prefix = validated_prefix(&prefix.into())?;
             }
             Address::Derived(derived_address) => {
-                derived_address.prefix = ArrayString::new(&prefix.into())?;
+                derived_address.prefix = validated_prefix(&prefix.into())?;
             }
         }
         Ok(())
     }
 
+    /// Returns a copy of this address with a different `prefix`, leaving the original unchanged.
+    /// Useful for translating an address between chains that share a key derivation path
+    pub fn with_prefix(&self, prefix: &str) -> Result<Address, AddressError> {
+        let mut address = *self;
+        address.change_prefix(prefix)?;
+        Ok(address)
+    }
+
+    /// Returns the validator operator form of this address, `base_prefix` is the account prefix
+    /// of the chain, for example 'cosmos' produces a 'cosmosvaloper' address
+    pub fn to_valoper(&self, base_prefix: &str) -> Result<Address, AddressError> {
+        self.with_prefix(&format!("{base_prefix}{VALOPER_SUFFIX}"))
+    }
+
+    /// Returns the account form of a valoper address by removing the suffix from its prefix,
+    /// other addresses are returned unchanged. Valcons addresses are derived from the consensus
+    /// key of a validator rather than its operator account and have no account form
+    pub fn to_account(&self) -> Result<Address, AddressError> {
+        let prefix = self.get_prefix();
+        match prefix.strip_suffix(VALOPER_SUFFIX) {
+            Some(base_prefix) => self.with_prefix(base_prefix),
+            None => Ok(*self),
+        }
+    }
+
+    /// Renders this address as an EIP-55 checksummed `0x` prefixed Ethereum address, as used by
+    /// Ethermint chains. Only valid for 20 byte Base addresses
+    #[cfg(feature = "ethermint")]
+    pub fn to_eth_checksum_string(&self) -> Result<String, AddressError> {
+        let bytes = match self {
+            Address::Base(base_address) => base_address.bytes,
+            Address::Derived(_) => return Err(AddressError::BytesDecodeErrorWrongLength),
+        };
+        let lower: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+        let hash = sha3::Keccak256::digest(lower.as_bytes());
+
+        let mut out = String::with_capacity(42);
+        out.push_str("0x");
+        for (i, c) in lower.chars().enumerate() {
+            // each hex char is checked against the matching nibble of the hash
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                out.push(c.to_ascii_uppercase());
+            } else {
+                out.push(c);
+            }
+        }
+        Ok(out)
+    }
+
     /// Returns the underlying `bytes` buffer as a slice
     pub fn get_bytes(&self) -> &[u8] {
         match self {
@@ -200,7 +253,7 @@ impl BaseAddress {
     pub fn from_bytes<T: Into<String>>(bytes: [u8; 20], prefix: T) -> Result<Self, AddressError> {
         Ok(Self {
             bytes,
-            prefix: ArrayString::new(&prefix.into())?,
+            prefix: validated_prefix(&prefix.into())?,
         })
     }
 }
@@ -209,11 +262,28 @@ impl DerivedAddress {
     pub fn from_bytes<T: Into<String>>(bytes: [u8; 32], prefix: T) -> Result<Self, AddressError> {
         Ok(Self {
             bytes,
-            prefix: ArrayString::new(&prefix.into())?,
+            prefix: validated_prefix(&prefix.into())?,
         })
     }
 }
 
+/// Checks that `prefix` can be used as a Bech32 `hrp`, since bech32 itself would only reject it
+/// much later when encoding. Prefixes must be non empty and contain only lowercase ascii letters
+pub fn validate_prefix(prefix: &str) -> Result<(), AddressError> {
+    if prefix.is_empty() {
+        return Err(AddressError::EmptyPrefix);
+    }
+    match prefix.chars().find(|c| !c.is_ascii_lowercase()) {
+        Some(c) => Err(AddressError::InvalidPrefixCharacter(c)),
+        None => Ok(()),
+    }
+}
+
+fn validated_prefix(prefix: &str) -> Result<ArrayString, AddressError> {
+    validate_prefix(prefix)?;
+    Ok(ArrayString::new(prefix)?)
+}
+
 // Locally computes the address for a Cosmos ModuleAccount, which is the first 20 bytes of
 // the sha256 hash of the name of the module.
 // See Module() for more info: https://github.com/cosmos/cosmos-sdk/blob/main/types/address/hash.go
@@ -234,6 +304,39 @@ pub fn get_module_account_address(
     Address::from_slice(&result[0..20], prefix)
 }
 
+/// The port prefix used by ICA controllers, the full port id is this prefix plus the owner address
+pub const ICA_CONTROLLER_PORT_PREFIX: &str = "icacontroller-";
+
+// Locally computes the interchain account address on the host chain for the given `owner` on the
+// controller chain and the controller side `connection_id`. This is address.Derive() in the sdk,
+// sha256(sha256(module_address) || key) with the ICA module account and a key of the connection id
+// and port id concatenated.
+// See GenerateAddress() for more info: https://github.com/cosmos/ibc-go/blob/v3.3.0/modules/apps/27-interchain-accounts/types/account.go#L42-L47
+pub fn derive_ica_address(
+    owner: &Address,
+    connection_id: &str,
+    prefix: &str,
+) -> Result<Address, AddressError> {
+    let module_address = get_module_account_address("interchainaccounts", None)?;
+    let port_id = format!("{ICA_CONTROLLER_PORT_PREFIX}{owner}");
+
+    let type_hash = Sha256::digest(module_address.get_bytes());
+    let mut hasher = Sha256::new();
+    hasher.update(type_hash);
+    hasher.update(connection_id.as_bytes());
+    hasher.update(port_id.as_bytes());
+    let result = hasher.finalize();
+
+    Address::from_slice(&result, prefix)
+}
+
+/// Parses a bech32 encoded address and re-encodes it with a new prefix in one step
+/// e.g. `cosmos1...` to `osmo1...`
+pub fn convert_bech32_prefix(input: &str, new_prefix: &str) -> Result<String, AddressError> {
+    let address = Address::from_bech32(input.to_string())?;
+    Ok(address.with_prefix(new_prefix)?.to_string())
+}
+
 #[cfg(feature = "ethermint")]
 // Swaps the byte interpretation of an address from CosmosAddress to EthAddress
 pub fn cosmos_address_to_eth_address(
@@ -287,6 +390,90 @@ fn test_parse() {
         .unwrap();
 }
 
+#[test]
+fn test_prefix_conversion() {
+    let address: Address = "cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp"
+        .parse()
+        .unwrap();
+    let osmo = address.with_prefix("osmo").unwrap();
+    assert_eq!(address.get_prefix(), "cosmos");
+    assert_eq!(osmo.get_prefix(), "osmo");
+    assert_eq!(osmo.get_bytes(), address.get_bytes());
+
+    let converted =
+        convert_bech32_prefix("cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp", "osmo").unwrap();
+    assert_eq!(converted, osmo.to_string());
+    assert_eq!(
+        convert_bech32_prefix(&converted, "cosmos").unwrap(),
+        "cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp"
+    );
+
+    assert!(address
+        .with_prefix("aprefixthatisfartoolongtobevalidforbech32")
+        .is_err());
+    assert!(convert_bech32_prefix("notanaddress", "osmo").is_err());
+}
+
+#[test]
+fn test_validator_prefixes() {
+    let address: Address = "cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp"
+        .parse()
+        .unwrap();
+    let valoper = address.to_valoper("cosmos").unwrap();
+    assert_eq!(valoper.get_prefix(), "cosmosvaloper");
+    assert_eq!(valoper.get_bytes(), address.get_bytes());
+
+    assert_eq!(valoper.to_account().unwrap(), address);
+    assert_eq!(address.to_account().unwrap(), address);
+    // consensus addresses are not derived from the operator account
+    let valcons = address.with_prefix("cosmosvalcons").unwrap();
+    assert_eq!(valcons.to_account().unwrap(), valcons);
+
+    assert!(address.to_valoper("Cosmos").is_err());
+}
+
+#[test]
+fn test_derive_ica_address() {
+    let owner: Address = "cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp"
+        .parse()
+        .unwrap();
+    let ica = derive_ica_address(&owner, "connection-0", "osmo").unwrap();
+    assert!(matches!(ica, Address::Derived(_)));
+    assert_eq!(
+        ica.to_string(),
+        "osmo12hp7hje5f079f0v7nju2prn8w9vcs6elr2v8xeqq7uq7ed79p3wq30w7dq"
+    );
+    // the connection id is part of the derivation key
+    assert_ne!(
+        derive_ica_address(&owner, "connection-1", "osmo").unwrap(),
+        ica
+    );
+}
+
+#[test]
+fn test_prefix_validation() {
+    assert!(matches!(
+        Address::from_slice(&[0; 20], "Cosmos"),
+        Err(AddressError::InvalidPrefixCharacter('C'))
+    ));
+    assert!(matches!(
+        Address::from_slice(&[0; 20], "cos1mos"),
+        Err(AddressError::InvalidPrefixCharacter('1'))
+    ));
+    assert!(matches!(
+        Address::from_slice(&[0; 32], ""),
+        Err(AddressError::EmptyPrefix)
+    ));
+
+    let mut address = Address::from_slice(&[0; 20], "cosmos").unwrap();
+    assert!(matches!(
+        address.change_prefix("gravity-bridge"),
+        Err(AddressError::InvalidPrefixCharacter('-'))
+    ));
+    assert_eq!(address.get_prefix(), "cosmos");
+    assert!(address.with_prefix("").is_err());
+}
+
 #[cfg(feature = "ethermint")]
 #[test]
 fn test_address_conversion() {
@@ -296,3 +483,18 @@ fn test_address_conversion() {
     let eth_address = cosmos_address_to_eth_address(test).unwrap();
     let _cosmos_address = eth_address_to_cosmos_address(eth_address, None).unwrap();
 }
+
+#[cfg(feature = "ethermint")]
+#[test]
+fn test_eth_checksum_string() {
+    // test vector from EIP-55
+    let bytes = hex_str_to_bytes("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap();
+    let address = Address::from_slice(&bytes, "evmos").unwrap();
+    assert_eq!(
+        address.to_eth_checksum_string().unwrap(),
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+
+    let derived = Address::from_slice(&[1; 32], "evmos").unwrap();
+    assert!(derived.to_eth_checksum_string().is_err());
+}
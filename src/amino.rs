@@ -0,0 +1,199 @@
+//! Contains the legacy amino JSON sign doc used by SIGN_MODE_LEGACY_AMINO_JSON, which is
+//! required by hardware wallets such as the Ledger that can not parse protobuf transactions
+
+use crate::client::type_urls::MSG_SEND_TYPE_URL;
+use crate::coin::{Coin, Fee};
+use crate::msg::Msg;
+use crate::private_key::MessageArgs;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use serde_json::{json, Value};
+
+/// A message in the legacy amino JSON format, `msg_type` is the amino name of the message
+/// such as 'cosmos-sdk/MsgSend' and `value` holds its fields. This must be exactly the JSON
+/// the chain produces for the matching protobuf message or the signature will not verify
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AminoMsg {
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    pub value: Value,
+}
+
+/// A message that can be signed both in protobuf form and in amino JSON form, bridging the
+/// protobuf Msg used by SIGN_MODE_DIRECT and the AminoMsg used by SIGN_MODE_LEGACY_AMINO_JSON.
+/// The amino form can not be derived from an arbitrary Msg since it requires knowing the
+/// message type, so this is implemented for each supported message type
+pub trait DeepSpaceMsg {
+    /// Returns the protobuf form of this message
+    fn to_msg(&self) -> Msg;
+
+    /// Returns the amino JSON form of this message
+    fn to_amino_msg(&self) -> AminoMsg;
+}
+
+impl DeepSpaceMsg for MsgSend {
+    fn to_msg(&self) -> Msg {
+        Msg::new(MSG_SEND_TYPE_URL, self.clone())
+    }
+
+    fn to_amino_msg(&self) -> AminoMsg {
+        let amount: Vec<Value> = self
+            .amount
+            .iter()
+            .map(|c| json!({"amount": c.amount, "denom": c.denom}))
+            .collect();
+        AminoMsg {
+            msg_type: "cosmos-sdk/MsgSend".to_string(),
+            value: json!({
+                "amount": amount,
+                "from_address": self.from_address,
+                "to_address": self.to_address,
+            }),
+        }
+    }
+}
+
+/// The amino JSON representation of a transaction fee
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct StdFee {
+    amount: Vec<Coin>,
+    gas: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    payer: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    granter: Option<String>,
+}
+
+impl From<Fee> for StdFee {
+    fn from(value: Fee) -> Self {
+        StdFee {
+            amount: value.amount,
+            gas: value.gas_limit.to_string(),
+            payer: value.payer.map(|v| v.to_string()),
+            granter: value.granter,
+        }
+    }
+}
+
+/// The document signed in SIGN_MODE_LEGACY_AMINO_JSON, integers are encoded as strings
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+struct StdSignDoc {
+    account_number: String,
+    chain_id: String,
+    fee: StdFee,
+    memo: String,
+    msgs: Vec<AminoMsg>,
+    sequence: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timeout_height: Option<String>,
+}
+
+/// Produces the bytes signed in SIGN_MODE_LEGACY_AMINO_JSON for the given messages, this is
+/// the canonical JSON of the StdSignDoc, with sorted keys and no whitespace
+pub fn std_sign_doc_bytes(msgs: &[AminoMsg], args: &MessageArgs, memo: &str) -> Vec<u8> {
+    let sign_doc = StdSignDoc {
+        account_number: args.account_number.to_string(),
+        chain_id: args.chain_id.clone(),
+        fee: args.fee.clone().into(),
+        memo: memo.to_string(),
+        msgs: msgs.to_vec(),
+        sequence: args.sequence.to_string(),
+        timeout_height: match args.timeout_height {
+            0 => None,
+            height => Some(height.to_string()),
+        },
+    };
+    // converting to a Value first sorts all object keys
+    let value = serde_json::to_value(sign_doc).unwrap();
+    canonical_json(&value).into_bytes()
+}
+
+/// Serializes a JSON value the way the Cosmos SDK sorts and escapes JSON before signing,
+/// keys are sorted and the HTML characters <, >, and & are escaped as go's encoder does
+fn canonical_json(value: &Value) -> String {
+    serde_json::to_string(value)
+        .unwrap()
+        .replace('<', "\\u003c")
+        .replace('>', "\\u003e")
+        .replace('&', "\\u0026")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::private_key::test_message_args;
+    use crate::{CosmosPrivateKey, PrivateKey};
+    use cosmos_sdk_proto::cosmos::tx::v1beta1::TxRaw;
+    use prost::Message;
+
+    #[test]
+    fn test_std_sign_doc_bytes() {
+        let msg = AminoMsg {
+            msg_type: "cosmos-sdk/MsgSend".to_string(),
+            value: json!({
+                "from_address": "cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp",
+                "to_address": "cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a",
+                "amount": [{"denom": "uatom", "amount": "1000"}]
+            }),
+        };
+        let args = MessageArgs {
+            sequence: 2,
+            fee: Fee {
+                amount: vec!["500uatom".parse().unwrap()],
+                gas_limit: 200_000,
+                payer: None,
+                granter: None,
+            },
+            timeout_height: 0,
+            chain_id: "cosmoshub-4".to_string(),
+            account_number: 7,
+            ..test_message_args()
+        };
+        let bytes = std_sign_doc_bytes(&[msg], &args, "a <memo> & more");
+        assert_eq!(
+            String::from_utf8(bytes).unwrap(),
+            concat!(
+                r#"{"account_number":"7","chain_id":"cosmoshub-4","#,
+                r#""fee":{"amount":[{"amount":"500","denom":"uatom"}],"gas":"200000"},"#,
+                r#""memo":"a \u003cmemo\u003e \u0026 more","#,
+                r#""msgs":[{"type":"cosmos-sdk/MsgSend","value":{"amount":[{"amount":"1000","denom":"uatom"}],"#,
+                r#""from_address":"cosmos1vlms2r8f6x7yxjh3ynyzc7ckarqd8a96ckjvrp","#,
+                r#""to_address":"cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a"}}],"sequence":"2"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_msg_send_amino_signing() {
+        let key = CosmosPrivateKey::from_secret(b"mySecret");
+        let address = key.to_address("cosmos").unwrap();
+        let send = MsgSend {
+            from_address: address.to_string(),
+            to_address: "cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a".to_string(),
+            amount: vec!["1000uatom".parse::<Coin>().unwrap().into()],
+        };
+        let amino_msg = send.to_amino_msg();
+        assert_eq!(
+            serde_json::to_string(&amino_msg).unwrap(),
+            format!(
+                concat!(
+                    r#"{{"type":"cosmos-sdk/MsgSend","value":{{"amount":[{{"amount":"1000","denom":"uatom"}}],"#,
+                    r#""from_address":"{}","to_address":"cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a"}}}}"#
+                ),
+                address
+            )
+        );
+
+        let args = MessageArgs {
+            fee: Fee::default(),
+            ..test_message_args()
+        };
+        let tx_bytes = key
+            .sign_std_msg_amino(&[send.to_msg()], &[amino_msg.clone()], args.clone(), "")
+            .unwrap();
+        let tx = TxRaw::decode(tx_bytes.as_slice()).unwrap();
+        assert!(key.to_public_key("cosmospub").unwrap().verify_bytes(
+            &std_sign_doc_bytes(&[amino_msg], &args, ""),
+            &tx.signatures[0]
+        ));
+    }
+}
@@ -3,13 +3,43 @@ use crate::client::types::BaseAccount;
 use crate::client::types::*;
 use crate::{client::Contact, error::CosmosGrpcError};
 use cosmos_sdk_proto::cosmos::auth::v1beta1::{
-    query_client::QueryClient as AuthQueryClient, QueryAccountRequest, QueryAccountsRequest,
+    query_client::QueryClient as AuthQueryClient, Params as AuthParams, QueryAccountRequest,
+    QueryAccountsRequest, QueryParamsRequest as QueryAuthParamsRequest,
 };
 use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
 use tokio::time::timeout;
 use tonic::Code as GrpcCode;
 
 impl Contact {
+    /// Gets the parameters of the auth module, such as the maximum memo length
+    pub async fn get_auth_params(&self) -> Result<AuthParams, CosmosGrpcError> {
+        let mut agrpc = grpc_client!(self, AuthQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            agrpc.params(self.query_request(QueryAuthParamsRequest {})),
+        )
+        .await??
+        .into_inner();
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No auth params returned".to_string(),
+            )),
+        }
+    }
+
+    /// Returns the MaxMemoCharacters auth param, this is queried once and then
+    /// cached, the cache is shared between all clones of this Contact
+    pub(crate) async fn get_max_memo_characters(&self) -> Result<u64, CosmosGrpcError> {
+        let cached = *self.max_memo_characters.lock().unwrap();
+        if let Some(max) = cached {
+            return Ok(max);
+        }
+        let max = self.get_auth_params().await?.max_memo_characters;
+        *self.max_memo_characters.lock().unwrap() = Some(max);
+        Ok(max)
+    }
+
     /// Gets account info for the provided Cosmos account using the accounts endpoint
     /// accounts do not have any info if they have no tokens or are otherwise never seen
     /// before in this case we return the special error NoToken
@@ -27,21 +57,18 @@ impl Contact {
         &self,
         address: Address,
     ) -> Result<AccountType, CosmosGrpcError> {
-        let mut agrpc = timeout(
-            self.get_timeout(),
-            AuthQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut agrpc = grpc_client!(self, AuthQueryClient);
         let query = QueryAccountRequest {
             address: address.to_bech32(&self.chain_prefix).unwrap(),
         };
-        let res = timeout(self.get_timeout(), agrpc.account(query)).await?;
+        let res = timeout(self.get_timeout(), agrpc.account(self.query_request(query))).await?;
         match res {
-            Ok(account) => {
-                // null pointer if this fails to unwrap
-                let value = account.into_inner().account.unwrap();
-                AccountType::decode_from_any(value)
-            }
+            Ok(account) => match account.into_inner().account {
+                Some(value) => AccountType::decode_from_any(value),
+                None => Err(CosmosGrpcError::BadResponse(
+                    "account field missing".to_string(),
+                )),
+            },
             Err(e) => match e.code() {
                 GrpcCode::NotFound => Err(CosmosGrpcError::NoToken),
                 _ => Err(CosmosGrpcError::RequestError { error: e }),
@@ -51,11 +78,7 @@ impl Contact {
 
     /// Gets account info for every account on the chain, a large query
     pub async fn get_all_accounts(&self) -> Result<Vec<AccountType>, CosmosGrpcError> {
-        let mut agrpc = timeout(
-            self.get_timeout(),
-            AuthQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut agrpc = grpc_client!(self, AuthQueryClient);
         // this response can be very large so we use pagination
         let mut page: PageRequest = PageRequest {
             key: Vec::new(),
@@ -70,7 +93,11 @@ impl Contact {
             let query = QueryAccountsRequest {
                 pagination: Some(page.clone()),
             };
-            let res = timeout(self.get_timeout(), agrpc.accounts(query)).await??;
+            let res = timeout(
+                self.get_timeout(),
+                agrpc.accounts(self.query_request(query)),
+            )
+            .await??;
             let res = res.into_inner();
 
             for value in res.accounts {
@@ -0,0 +1,172 @@
+//! Contains utility functions for interacting with the Cosmos sdk authz module, which
+//! allows an account to grant another account permission to execute messages on its behalf
+
+use super::send::TransactionResponse;
+use super::PAGE;
+use crate::client::type_urls::{
+    GENERIC_AUTHORIZATION_TYPE_URL, MSG_EXEC_TYPE_URL, MSG_GRANT_TYPE_URL, MSG_REVOKE_TYPE_URL,
+};
+use crate::error::CosmosGrpcError;
+use crate::utils::encode_any;
+use crate::{Address, Coin, Contact, Msg, PrivateKey};
+use cosmos_sdk_proto::cosmos::authz::v1beta1::query_client::QueryClient as AuthzQueryClient;
+use cosmos_sdk_proto::cosmos::authz::v1beta1::{
+    GenericAuthorization, Grant, GrantAuthorization, MsgExec, MsgGrant, MsgRevoke,
+    QueryGranterGrantsRequest, QueryGrantsRequest,
+};
+use prost_types::Timestamp;
+use std::time::{Duration, SystemTime};
+use tokio::time::timeout;
+
+impl Contact {
+    /// Gets the grants `granter` has made to `grantee`, optionally only those for messages
+    /// of type `msg_type_url`. The authorization of each grant is left encoded so that callers
+    /// can decode the specific authorization type they are interested in
+    pub async fn query_grants(
+        &self,
+        granter: Address,
+        grantee: Address,
+        msg_type_url: Option<String>,
+    ) -> Result<Vec<Grant>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, AuthzQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.grants(self.query_request(QueryGrantsRequest {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+                msg_type_url: msg_type_url.unwrap_or_default(),
+                pagination: PAGE,
+            })),
+        )
+        .await??
+        .into_inner();
+        Ok(res.grants)
+    }
+
+    /// Gets every grant made by `granter`, including the grantee of each
+    pub async fn query_granter_grants(
+        &self,
+        granter: Address,
+    ) -> Result<Vec<GrantAuthorization>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, AuthzQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.granter_grants(self.query_request(QueryGranterGrantsRequest {
+                granter: granter.to_string(),
+                pagination: PAGE,
+            })),
+        )
+        .await??
+        .into_inner();
+        Ok(res.grants)
+    }
+
+    /// Executes the provided messages as the grantee derived from `private_key`, the signer of
+    /// each message must be an account that has granted the grantee an authorization for it
+    pub async fn exec_authz(
+        &self,
+        msgs: Vec<Msg>,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        if msgs.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "No messages provided to exec_authz".to_string(),
+            ));
+        }
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let exec = MsgExec {
+            grantee: our_address.to_string(),
+            msgs: msgs.into_iter().map(|m| m.into()).collect(),
+        };
+
+        let msg = Msg::new(MSG_EXEC_TYPE_URL, exec);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Grants `grantee` permission to execute messages of type `msg_type_url` on behalf of
+    /// the account derived from `private_key`, for example "/cosmos.gov.v1beta1.MsgVote" to
+    /// let a hot key vote. If `expiration` is None the grant does not expire
+    #[allow(clippy::too_many_arguments)]
+    pub async fn grant_generic_authorization(
+        &self,
+        grantee: Address,
+        msg_type_url: String,
+        expiration: Option<SystemTime>,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let grant = MsgGrant {
+            granter: our_address.to_string(),
+            grantee: grantee.to_string(),
+            grant: Some(build_generic_grant(msg_type_url, expiration)),
+        };
+
+        let msg = Msg::new(MSG_GRANT_TYPE_URL, grant);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Revokes a grant previously made to `grantee` for messages of type `msg_type_url`
+    /// by the account derived from `private_key`
+    pub async fn revoke_authorization(
+        &self,
+        grantee: Address,
+        msg_type_url: String,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let revoke = MsgRevoke {
+            granter: our_address.to_string(),
+            grantee: grantee.to_string(),
+            msg_type_url,
+        };
+
+        let msg = Msg::new(MSG_REVOKE_TYPE_URL, revoke);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+}
+
+/// Builds a Grant containing a GenericAuthorization for the given message type
+fn build_generic_grant(msg_type_url: String, expiration: Option<SystemTime>) -> Grant {
+    let authorization = GenericAuthorization { msg: msg_type_url };
+    Grant {
+        authorization: Some(encode_any(authorization, GENERIC_AUTHORIZATION_TYPE_URL)),
+        expiration: expiration.map(Timestamp::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_build_generic_grant() {
+        let expiration = SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_500);
+        let grant =
+            build_generic_grant("/cosmos.gov.v1beta1.MsgVote".to_string(), Some(expiration));
+        assert_eq!(
+            grant.expiration,
+            Some(Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 500_000_000,
+            })
+        );
+        let authorization = grant.authorization.unwrap();
+        assert_eq!(authorization.type_url, GENERIC_AUTHORIZATION_TYPE_URL);
+        let authorization = GenericAuthorization::decode(authorization.value.as_slice()).unwrap();
+        assert_eq!(authorization.msg, "/cosmos.gov.v1beta1.MsgVote");
+
+        assert!(build_generic_grant(String::new(), None)
+            .expiration
+            .is_none());
+    }
+}
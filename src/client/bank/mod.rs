@@ -1,47 +1,133 @@
 //! Contains utilities and query endpoints for use with the Cosmos bank module
 //!
-use super::PAGE;
+use super::{collect_pages, PAGE};
+use crate::coin::validate_denom;
 use crate::error::CosmosGrpcError;
+use crate::utils::historical_grpc_query;
 use crate::{Address, Coin, Contact};
 use cosmos_sdk_proto::cosmos::bank::v1beta1::query_client::QueryClient as BankQueryClient;
 use cosmos_sdk_proto::cosmos::bank::v1beta1::{
-    Metadata, QueryDenomMetadataRequest, QueryDenomsMetadataRequest, QuerySupplyOfRequest,
-    QueryTotalSupplyRequest,
+    Metadata, QueryDenomMetadataRequest, QueryDenomOwnersRequest, QueryDenomsMetadataRequest,
+    QuerySupplyOfRequest, QueryTotalSupplyRequest,
+};
+use cosmos_sdk_proto::cosmos::bank::v1beta1::{
+    Params as BankParams, QueryParamsRequest, SendEnabled,
 };
 use cosmos_sdk_proto::cosmos::bank::v1beta1::{QueryAllBalancesRequest, QueryBalanceRequest};
 use tokio::time::timeout;
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+use tonic::Code as GrpcCode;
+
+/// The bank SendEnabled query added in Cosmos SDK 0.47, not included in cosmos-sdk-proto
+#[derive(Clone, PartialEq, prost::Message)]
+struct QuerySendEnabledRequest {
+    #[prost(string, repeated, tag = "1")]
+    denoms: Vec<String>,
+}
+
+/// The response to the bank SendEnabled query, only denoms with an explicit entry are returned
+#[derive(Clone, PartialEq, prost::Message)]
+struct QuerySendEnabledResponse {
+    #[prost(message, repeated, tag = "1")]
+    send_enabled: Vec<SendEnabled>,
+}
+
+const SEND_ENABLED_PATH: &str = "/cosmos.bank.v1beta1.Query/SendEnabled";
 
 impl Contact {
     /// gets the total supply of all coins on chain
     pub async fn query_total_supply(&self) -> Result<Vec<Coin>, CosmosGrpcError> {
-        let mut grpc = timeout(
+        let grpc = grpc_client!(self, BankQueryClient);
+        let supply = collect_pages(|page| {
+            let mut grpc = grpc.clone();
+            async move {
+                let res = timeout(
+                    self.get_timeout(),
+                    grpc.total_supply(self.query_request(QueryTotalSupplyRequest {
+                        pagination: Some(page),
+                    })),
+                )
+                .await??
+                .into_inner();
+                Ok::<_, CosmosGrpcError>((res.supply, res.pagination))
+            }
+        })
+        .await?;
+        Ok(supply.into_iter().map(|val| val.into()).collect())
+    }
+
+    /// Gets the bank module parameters, including which denoms have sending disabled
+    pub async fn get_bank_params(&self) -> Result<BankParams, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, BankQueryClient);
+        let res = timeout(
             self.get_timeout(),
-            BankQueryClient::connect(self.url.clone()),
+            grpc.params(self.query_request(QueryParamsRequest {})),
         )
-        .await??;
+        .await??
+        .into_inner();
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No bank params returned".to_string(),
+            )),
+        }
+    }
+
+    /// Checks if sending is enabled for each of the provided `denoms`, returning one
+    /// SendEnabled entry per denom in the same order. Since Cosmos SDK 0.47 the entries are
+    /// stored outside of the bank params and read with the SendEnabled query, on older chains
+    /// where that query is unimplemented they are read from the params. Denoms without an
+    /// explicit entry use the default send enabled value of the chain
+    pub async fn query_send_enabled(
+        &self,
+        denoms: Vec<String>,
+    ) -> Result<Vec<SendEnabled>, CosmosGrpcError> {
+        let params = self.get_bank_params().await?;
+        let entries = match self.query_send_enabled_entries(denoms.clone()).await {
+            Ok(entries) => entries,
+            Err(CosmosGrpcError::RequestError { error })
+                if error.code() == GrpcCode::Unimplemented =>
+            {
+                params.send_enabled.clone()
+            }
+            Err(e) => return Err(e),
+        };
+        Ok(send_enabled_for(
+            &entries,
+            params.default_send_enabled,
+            denoms,
+        ))
+    }
+
+    /// Queries the explicit send enabled entries for `denoms` using the SendEnabled query
+    async fn query_send_enabled_entries(
+        &self,
+        denoms: Vec<String>,
+    ) -> Result<Vec<SendEnabled>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, Grpc);
+        timeout(self.get_timeout(), grpc.ready()).await??;
         let res = timeout(
             self.get_timeout(),
-            grpc.total_supply(QueryTotalSupplyRequest { pagination: PAGE }),
+            grpc.unary(
+                self.query_request(QuerySendEnabledRequest { denoms }),
+                PathAndQuery::from_static(SEND_ENABLED_PATH),
+                ProstCodec::<QuerySendEnabledRequest, QuerySendEnabledResponse>::default(),
+            ),
         )
         .await??
         .into_inner();
-        let mut out = Vec::new();
-        for val in res.supply {
-            out.push(val.into())
-        }
-        Ok(out)
+        Ok(res.send_enabled)
     }
 
     /// gets the supply of an individual token
     pub async fn query_supply_of(&self, denom: String) -> Result<Option<Coin>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            BankQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        validate_denom(&denom).map_err(CosmosGrpcError::BadInput)?;
+        let mut grpc = grpc_client!(self, BankQueryClient);
         let res = timeout(
             self.get_timeout(),
-            grpc.supply_of(QuerySupplyOfRequest { denom }),
+            grpc.supply_of(self.query_request(QuerySupplyOfRequest { denom })),
         )
         .await??
         .into_inner();
@@ -53,18 +139,66 @@ impl Contact {
 
     /// Gets the denom metadata for every token type on the chain
     pub async fn get_all_denoms_metadata(&self) -> Result<Vec<Metadata>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            BankQueryClient::connect(self.url.clone()),
-        )
-        .await??;
-        let res = timeout(
-            self.get_timeout(),
-            grpc.denoms_metadata(QueryDenomsMetadataRequest { pagination: PAGE }),
-        )
-        .await??
-        .into_inner();
-        Ok(res.metadatas)
+        let grpc = grpc_client!(self, BankQueryClient);
+        collect_pages(|page| {
+            let mut grpc = grpc.clone();
+            async move {
+                let res = timeout(
+                    self.get_timeout(),
+                    grpc.denoms_metadata(self.query_request(QueryDenomsMetadataRequest {
+                        pagination: Some(page),
+                    })),
+                )
+                .await??
+                .into_inner();
+                Ok::<_, CosmosGrpcError>((res.metadatas, res.pagination))
+            }
+        })
+        .await
+    }
+
+    /// Gets every account holding `denom` along with its balance, this is far more efficient
+    /// than checking the balance of every account, for example when taking a snapshot
+    pub async fn query_denom_owners(
+        &self,
+        denom: String,
+    ) -> Result<Vec<(Address, Coin)>, CosmosGrpcError> {
+        validate_denom(&denom).map_err(CosmosGrpcError::BadInput)?;
+        let grpc = grpc_client!(self, BankQueryClient);
+        let owners = collect_pages(|page| {
+            let mut grpc = grpc.clone();
+            let denom = denom.clone();
+            async move {
+                let res = timeout(
+                    self.get_timeout(),
+                    grpc.denom_owners(self.query_request(QueryDenomOwnersRequest {
+                        denom,
+                        pagination: Some(page),
+                    })),
+                )
+                .await??
+                .into_inner();
+                Ok::<_, CosmosGrpcError>((res.denom_owners, res.pagination))
+            }
+        })
+        .await?;
+
+        let mut out = Vec::new();
+        for owner in owners {
+            let address: Address = owner
+                .address
+                .parse()
+                .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid owner address {e}")))?;
+            match owner.balance {
+                Some(balance) => out.push((address, balance.into())),
+                None => {
+                    return Err(CosmosGrpcError::BadResponse(format!(
+                        "No balance for denom owner {address}"
+                    )))
+                }
+            }
+        }
+        Ok(out)
     }
 
     /// Gets the denom metadata for a specific token
@@ -72,14 +206,11 @@ impl Contact {
         &self,
         denom: String,
     ) -> Result<Option<Metadata>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            BankQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        validate_denom(&denom).map_err(CosmosGrpcError::BadInput)?;
+        let mut grpc = grpc_client!(self, BankQueryClient);
         let res = timeout(
             self.get_timeout(),
-            grpc.denom_metadata(QueryDenomMetadataRequest { denom }),
+            grpc.denom_metadata(self.query_request(QueryDenomMetadataRequest { denom })),
         )
         .await??
         .into_inner();
@@ -88,19 +219,15 @@ impl Contact {
 
     /// Gets the coin balances for an individual account
     pub async fn get_balances(&self, address: Address) -> Result<Vec<Coin>, CosmosGrpcError> {
-        let mut bankrpc = timeout(
-            self.get_timeout(),
-            BankQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut bankrpc = grpc_client!(self, BankQueryClient);
         let res = timeout(
             self.get_timeout(),
-            bankrpc.all_balances(QueryAllBalancesRequest {
+            bankrpc.all_balances(self.query_request(QueryAllBalancesRequest {
                 // chain prefix is validated as part of this client, so this can't
                 // panic
                 address: address.to_bech32(&self.chain_prefix).unwrap(),
                 pagination: PAGE,
-            }),
+            })),
         )
         .await??
         .into_inner();
@@ -112,25 +239,50 @@ impl Contact {
         Ok(ret)
     }
 
+    /// Gets the coin balances for an individual account as of the given block `height`
+    /// the node must have the state for that height available, if it has been pruned
+    /// the node will respond with an error
+    pub async fn get_balances_at_height(
+        &self,
+        address: Address,
+        height: u64,
+    ) -> Result<Vec<Coin>, CosmosGrpcError> {
+        let mut bankrpc = grpc_client!(self, BankQueryClient);
+        let request = historical_grpc_query(
+            QueryAllBalancesRequest {
+                // chain prefix is validated as part of this client, so this can't
+                // panic
+                address: address.to_bech32(&self.chain_prefix).unwrap(),
+                pagination: PAGE,
+            },
+            height,
+        );
+        let res = timeout(self.get_timeout(), bankrpc.all_balances(request))
+            .await??
+            .into_inner();
+        let mut ret = Vec::new();
+        for value in res.balances {
+            ret.push(value.into());
+        }
+        Ok(ret)
+    }
+
     /// Gets the balance of a single for an individual account
     pub async fn get_balance(
         &self,
         address: Address,
         denom: String,
     ) -> Result<Option<Coin>, CosmosGrpcError> {
-        let mut bankrpc = timeout(
-            self.get_timeout(),
-            BankQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        validate_denom(&denom).map_err(CosmosGrpcError::BadInput)?;
+        let mut bankrpc = grpc_client!(self, BankQueryClient);
         let res = timeout(
             self.get_timeout(),
-            bankrpc.balance(QueryBalanceRequest {
+            bankrpc.balance(self.query_request(QueryBalanceRequest {
                 // chain prefix is validated as part of this client, so this can't
                 // panic
                 address: address.to_bech32(&self.chain_prefix).unwrap(),
                 denom,
-            }),
+            })),
         )
         .await??
         .into_inner();
@@ -140,3 +292,61 @@ impl Contact {
         }
     }
 }
+
+/// Resolves the send enabled status of each denom from the explicit `entries`, falling back
+/// to `default_send_enabled` for denoms without one
+fn send_enabled_for(
+    entries: &[SendEnabled],
+    default_send_enabled: bool,
+    denoms: Vec<String>,
+) -> Vec<SendEnabled> {
+    denoms
+        .into_iter()
+        .map(|denom| {
+            let enabled = entries
+                .iter()
+                .find(|v| v.denom == denom)
+                .map(|v| v.enabled)
+                .unwrap_or(default_send_enabled);
+            SendEnabled { denom, enabled }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_send_enabled_for() {
+        let entries = vec![SendEnabled {
+            denom: "ustake".to_string(),
+            enabled: false,
+        }];
+        let res = send_enabled_for(
+            &entries,
+            true,
+            vec!["ustake".to_string(), "uatom".to_string()],
+        );
+        assert_eq!(
+            res,
+            vec![
+                SendEnabled {
+                    denom: "ustake".to_string(),
+                    enabled: false
+                },
+                SendEnabled {
+                    denom: "uatom".to_string(),
+                    enabled: true
+                }
+            ]
+        );
+        // with sending disabled by default only explicit entries are enabled
+        let entries = vec![SendEnabled {
+            denom: "ustake".to_string(),
+            enabled: true,
+        }];
+        let res = send_enabled_for(&entries, false, vec!["uatom".to_string()]);
+        assert!(!res[0].enabled);
+    }
+}
@@ -4,22 +4,30 @@
 use super::send::TransactionResponse;
 use super::{ChainStatus, PAGE};
 use crate::client::type_urls::{
-    MSG_FUND_COMMUNITY_POOL_TYPE_URL, MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL,
-    MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL,
+    MSG_FUND_COMMUNITY_POOL_TYPE_URL, MSG_SET_WITHDRAW_ADDRESS_TYPE_URL,
+    MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL, MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL,
 };
+use crate::decimal::WideDecimal;
 use crate::error::CosmosGrpcError;
 use crate::{Address, Coin, Contact, Msg, PrivateKey};
 use cosmos_sdk_proto::cosmos::base::v1beta1::DecCoin;
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::query_client::QueryClient as DistQueryClient;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::QueryValidatorOutstandingRewardsRequest;
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
     MsgFundCommunityPool, QueryValidatorSlashesRequest,
 };
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
+    MsgSetWithdrawAddress, QueryDelegatorWithdrawAddressRequest,
+};
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
     MsgWithdrawDelegatorReward, ValidatorSlashEvent,
 };
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
     MsgWithdrawValidatorCommission, QueryDelegationRewardsRequest,
 };
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
+    Params as DistributionParams, QueryParamsRequest as QueryDistributionParamsRequest,
+};
 use cosmos_sdk_proto::cosmos::distribution::v1beta1::{
     QueryCommunityPoolRequest, QueryDelegationTotalRewardsRequest,
 };
@@ -39,17 +47,7 @@ impl Contact {
     /// are in DecCoins for precision, for the sake of ease of use this endpoint converts them
     /// into their normal form, for easy comparison against any other coin or amount.
     pub async fn query_community_pool(&self) -> Result<Vec<Coin>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            DistQueryClient::connect(self.url.clone()),
-        )
-        .await??;
-        let res = timeout(
-            self.get_timeout(),
-            grpc.community_pool(QueryCommunityPoolRequest {}),
-        )
-        .await??;
-        let val = res.into_inner().pool;
+        let val = self.query_community_pool_raw().await?;
         let mut res = Vec::new();
         for v in val {
             let parse_result: Result<Uint256, ParseError> = v.amount.parse();
@@ -64,16 +62,58 @@ impl Contact {
         Ok(res)
     }
 
+    /// Gets the coins in the community pool with their full 18 digits of precision, unlike
+    /// `query_community_pool` which truncates the fractional part
+    pub async fn query_community_pool_dec(
+        &self,
+    ) -> Result<Vec<(String, WideDecimal)>, CosmosGrpcError> {
+        let val = self.query_community_pool_raw().await?;
+        let mut res = Vec::new();
+        for v in val {
+            let amount = WideDecimal::from_raw_sdk_dec(&v.amount).map_err(|e| {
+                CosmosGrpcError::BadResponse(format!("Invalid amount of {} {e}", v.denom))
+            })?;
+            res.push((v.denom, amount));
+        }
+        Ok(res)
+    }
+
+    /// Queries the community pool returning the DecCoins with raw sdk.Dec amounts
+    async fn query_community_pool_raw(&self) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, DistQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.community_pool(self.query_request(QueryCommunityPoolRequest {})),
+        )
+        .await??;
+        Ok(res.into_inner().pool)
+    }
+
+    /// Gets the parameters of the distribution module, such as the community tax and
+    /// proposer rewards. Note these values are sdk.Dec in their raw integer form, see
+    /// `Decimal::from_raw_sdk_dec`
+    pub async fn get_distribution_params(&self) -> Result<DistributionParams, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, DistQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.params(self.query_request(QueryDistributionParamsRequest {})),
+        )
+        .await??
+        .into_inner();
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No distribution params returned".to_string(),
+            )),
+        }
+    }
+
     /// Gets the slashing events of a validator starting from Genesis to the current block height
     pub async fn query_validator_slashes(
         &self,
         validator_address: impl ToString,
     ) -> Result<Vec<ValidatorSlashEvent>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            DistQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, DistQueryClient);
         let current_block = self.get_chain_status().await?;
         let current_block = match current_block {
             ChainStatus::Moving { block_height } => block_height,
@@ -82,12 +122,12 @@ impl Contact {
 
         let res = timeout(
             self.get_timeout(),
-            grpc.validator_slashes(QueryValidatorSlashesRequest {
+            grpc.validator_slashes(self.query_request(QueryValidatorSlashesRequest {
                 validator_address: validator_address.to_string(),
                 starting_height: 0,
                 ending_height: current_block,
                 pagination: PAGE,
-            }),
+            })),
         )
         .await??
         .into_inner();
@@ -118,16 +158,12 @@ impl Contact {
         &self,
         delegator_address: Address,
     ) -> Result<Vec<String>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            DistQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, DistQueryClient);
         let res = timeout(
             self.get_timeout(),
-            grpc.delegator_validators(QueryDelegatorValidatorsRequest {
+            grpc.delegator_validators(self.query_request(QueryDelegatorValidatorsRequest {
                 delegator_address: delegator_address.to_string(),
-            }),
+            })),
         )
         .await??
         .into_inner();
@@ -140,17 +176,13 @@ impl Contact {
         delegator_address: Address,
         validator_address: Address,
     ) -> Result<Vec<DecCoin>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            DistQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, DistQueryClient);
         let res = timeout(
             self.get_timeout(),
-            grpc.delegation_rewards(QueryDelegationRewardsRequest {
+            grpc.delegation_rewards(self.query_request(QueryDelegationRewardsRequest {
                 delegator_address: delegator_address.to_string(),
                 validator_address: validator_address.to_string(),
-            }),
+            })),
         )
         .await??
         .into_inner()
@@ -163,16 +195,12 @@ impl Contact {
         &self,
         delegator_address: Address,
     ) -> Result<QueryDelegationTotalRewardsResponse, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            DistQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, DistQueryClient);
         let res = timeout(
             self.get_timeout(),
-            grpc.delegation_total_rewards(QueryDelegationTotalRewardsRequest {
+            grpc.delegation_total_rewards(self.query_request(QueryDelegationTotalRewardsRequest {
                 delegator_address: delegator_address.to_string(),
-            }),
+            })),
         )
         .await??
         .into_inner();
@@ -206,6 +234,26 @@ impl Contact {
             .await
     }
 
+    /// Gets the rewards of a validator that have not yet been withdrawn by its delegators
+    /// or as commission, note these are DecCoins in their raw sdk.Dec form
+    pub async fn query_validator_outstanding_rewards(
+        &self,
+        validator: Address,
+    ) -> Result<Vec<DecCoin>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, DistQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.validator_outstanding_rewards(self.query_request(
+                QueryValidatorOutstandingRewardsRequest {
+                    validator_address: validator.to_string(),
+                },
+            )),
+        )
+        .await??
+        .into_inner();
+        Ok(res.rewards.map(|r| r.rewards).unwrap_or_default())
+    }
+
     /// Withdraws commission from the provided validator
     pub async fn withdraw_validator_commission(
         &self,
@@ -223,6 +271,48 @@ impl Contact {
             .await
     }
 
+    /// Sets the address the distribution rewards of the delegator derived from `private_key` are
+    /// paid out to, for example to route staking rewards to a cold wallet
+    pub async fn set_withdraw_address(
+        &self,
+        withdraw_address: Address,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let msg = MsgSetWithdrawAddress {
+            delegator_address: our_address.to_string(),
+            withdraw_address: withdraw_address.to_string(),
+        };
+
+        let msg = Msg::new(MSG_SET_WITHDRAW_ADDRESS_TYPE_URL, msg);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Gets the address the distribution rewards of the given delegator are paid out to,
+    /// this is the delegator itself unless it has been changed with `set_withdraw_address`
+    pub async fn get_withdraw_address(
+        &self,
+        delegator_address: Address,
+    ) -> Result<Address, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, DistQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.delegator_withdraw_address(self.query_request(
+                QueryDelegatorWithdrawAddressRequest {
+                    delegator_address: delegator_address.to_string(),
+                },
+            )),
+        )
+        .await??
+        .into_inner();
+        res.withdraw_address
+            .parse()
+            .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid withdraw address {e}")))
+    }
+
     /// Sends the specified funds directly to the community pool
     pub async fn fund_community_pool(
         &self,
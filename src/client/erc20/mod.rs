@@ -0,0 +1,110 @@
+//! Contains utility functions for interacting with the erc20 module, which pairs Cosmos coins
+//! with ERC20 tokens on Althea and other Canto derived chains
+
+use super::collect_pages;
+use super::send::TransactionResponse;
+use crate::address::cosmos_address_to_eth_address;
+use crate::client::type_urls::{MSG_CONVERT_COIN_TYPE_URL, MSG_CONVERT_ERC20_TYPE_URL};
+use crate::error::CosmosGrpcError;
+use crate::{Address, Coin, Contact, Msg, PrivateKey};
+use althea_proto::canto::erc20::v1::query_client::QueryClient as Erc20QueryClient;
+use althea_proto::canto::erc20::v1::{
+    MsgConvertCoin, MsgConvertErc20, QueryTokenPairRequest, QueryTokenPairsRequest, TokenPair,
+};
+use clarity::Address as EthAddress;
+use num256::Uint256;
+use std::time::Duration;
+use tokio::time::timeout;
+use tonic::Code as GrpcCode;
+
+impl Contact {
+    /// Converts `coin` into its paired ERC20 token, which is sent to `receiver_eth`. The
+    /// coin must have a registered token pair, see `query_token_pair`
+    pub async fn convert_coin(
+        &self,
+        coin: Coin,
+        receiver_eth: EthAddress,
+        fee: Coin,
+        key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = key.to_address(&self.chain_prefix).unwrap();
+        let convert = MsgConvertCoin {
+            coin: Some(coin.into()),
+            receiver: receiver_eth.to_string(),
+            sender: our_address.to_string(),
+        };
+
+        let msg = Msg::new(MSG_CONVERT_COIN_TYPE_URL, convert);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, key)
+            .await
+    }
+
+    /// Converts `amount` of the ERC20 token at `contract` held by the Ethereum address of
+    /// `key` into its paired Cosmos coin, which is sent to `receiver_cosmos`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn convert_erc20(
+        &self,
+        contract: EthAddress,
+        amount: Uint256,
+        receiver_cosmos: Address,
+        fee: Coin,
+        key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = key.to_address(&self.chain_prefix).unwrap();
+        let our_eth_address = cosmos_address_to_eth_address(our_address)
+            .map_err(|e| CosmosGrpcError::BadInput(format!("No Ethereum address for key {e}")))?;
+        let convert = MsgConvertErc20 {
+            contract_address: contract.to_string(),
+            amount: amount.to_string(),
+            receiver: receiver_cosmos.to_string(),
+            sender: our_eth_address.to_string(),
+        };
+
+        let msg = Msg::new(MSG_CONVERT_ERC20_TYPE_URL, convert);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, key)
+            .await
+    }
+
+    /// Gets every registered token pair
+    pub async fn query_token_pairs(&self) -> Result<Vec<TokenPair>, CosmosGrpcError> {
+        let grpc = grpc_client!(self, Erc20QueryClient);
+        collect_pages(|page| {
+            let mut grpc = grpc.clone();
+            async move {
+                let res = timeout(
+                    self.get_timeout(),
+                    grpc.token_pairs(self.query_request(QueryTokenPairsRequest {
+                        pagination: Some(page),
+                    })),
+                )
+                .await??
+                .into_inner();
+                Ok::<_, CosmosGrpcError>((res.token_pairs, res.pagination))
+            }
+        })
+        .await
+    }
+
+    /// Gets the token pair for `token`, which may be either the Cosmos denom or the ERC20
+    /// contract address, returns None if no pair is registered for it
+    pub async fn query_token_pair(
+        &self,
+        token: String,
+    ) -> Result<Option<TokenPair>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, Erc20QueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.token_pair(self.query_request(QueryTokenPairRequest { token })),
+        )
+        .await?;
+        match res {
+            Ok(res) => Ok(res.into_inner().token_pair),
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+}
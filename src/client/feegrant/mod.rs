@@ -0,0 +1,95 @@
+//! Contains utility functions for interacting with the Cosmos sdk feegrant module, which
+//! allows an account to pay the transaction fees of another account
+
+use super::send::TransactionResponse;
+use crate::client::type_urls::{
+    BASIC_ALLOWANCE_TYPE_URL, MSG_GRANT_ALLOWANCE_TYPE_URL, MSG_REVOKE_ALLOWANCE_TYPE_URL,
+};
+use crate::error::CosmosGrpcError;
+use crate::utils::encode_any;
+use crate::{Address, Coin, Contact, Msg, PrivateKey};
+use cosmos_sdk_proto::cosmos::feegrant::v1beta1::query_client::QueryClient as FeegrantQueryClient;
+use cosmos_sdk_proto::cosmos::feegrant::v1beta1::{
+    BasicAllowance, Grant, MsgGrantAllowance, MsgRevokeAllowance, QueryAllowanceRequest,
+};
+use prost_types::Timestamp;
+use std::time::{Duration, SystemTime};
+use tokio::time::timeout;
+use tonic::Code as GrpcCode;
+
+impl Contact {
+    /// Grants `grantee` an allowance to pay transaction fees from the account derived from
+    /// `private_key`, up to `spend_limit` in total. An empty `spend_limit` places no limit on
+    /// the fees paid and if `expiration` is None the allowance does not expire. The grantee
+    /// uses the allowance by setting the fee granter of its transactions to the granter
+    #[allow(clippy::too_many_arguments)]
+    pub async fn grant_fee_allowance(
+        &self,
+        grantee: Address,
+        spend_limit: Vec<Coin>,
+        expiration: Option<SystemTime>,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let allowance = BasicAllowance {
+            spend_limit: spend_limit.into_iter().map(|c| c.into()).collect(),
+            expiration: expiration.map(Timestamp::from),
+        };
+        let grant = MsgGrantAllowance {
+            granter: our_address.to_string(),
+            grantee: grantee.to_string(),
+            allowance: Some(encode_any(allowance, BASIC_ALLOWANCE_TYPE_URL)),
+        };
+
+        let msg = Msg::new(MSG_GRANT_ALLOWANCE_TYPE_URL, grant);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Revokes the fee allowance previously granted to `grantee` by the account
+    /// derived from `private_key`
+    pub async fn revoke_fee_allowance(
+        &self,
+        grantee: Address,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let revoke = MsgRevokeAllowance {
+            granter: our_address.to_string(),
+            grantee: grantee.to_string(),
+        };
+
+        let msg = Msg::new(MSG_REVOKE_ALLOWANCE_TYPE_URL, revoke);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Gets the fee allowance `granter` has given `grantee`, returns None if there is none.
+    /// The allowance is left encoded since several allowance types exist
+    pub async fn query_fee_allowance(
+        &self,
+        granter: Address,
+        grantee: Address,
+    ) -> Result<Option<Grant>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, FeegrantQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.allowance(self.query_request(QueryAllowanceRequest {
+                granter: granter.to_string(),
+                grantee: grantee.to_string(),
+            })),
+        )
+        .await?;
+        match res {
+            Ok(res) => Ok(res.into_inner().allowance),
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+}
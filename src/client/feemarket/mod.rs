@@ -0,0 +1,103 @@
+//! Contains utility functions for querying the Ethermint feemarket module, which implements an
+//! EIP-1559 style base fee on Ethermint chains such as Evmos and Althea
+
+use crate::error::CosmosGrpcError;
+use crate::{Coin, Contact, Msg, PrivateKey};
+use num256::Uint256;
+use num_traits::CheckedMul;
+use tokio::time::timeout;
+use tonic::client::Grpc;
+use tonic::codec::ProstCodec;
+use tonic::codegen::http::uri::PathAndQuery;
+
+/// The feemarket BaseFee query, not included in cosmos-sdk-proto
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryBaseFeeRequest {}
+
+/// The response to the feemarket BaseFee query, `base_fee` is an sdk.Int and
+/// is empty if the base fee is disabled
+#[derive(Clone, PartialEq, prost::Message)]
+struct QueryBaseFeeResponse {
+    #[prost(string, tag = "1")]
+    base_fee: String,
+}
+
+const BASE_FEE_PATH: &str = "/ethermint.feemarket.v1.Query/BaseFee";
+
+impl Contact {
+    /// Gets the current base fee of the feemarket module, this is the minimum price per unit
+    /// of gas, in the evm denom, a transaction must pay to be included in the next block
+    pub async fn get_base_fee(&self) -> Result<Uint256, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, Grpc);
+        timeout(self.get_timeout(), grpc.ready()).await??;
+        let res = timeout(
+            self.get_timeout(),
+            grpc.unary(
+                self.query_request(QueryBaseFeeRequest {}),
+                PathAndQuery::from_static(BASE_FEE_PATH),
+                ProstCodec::<QueryBaseFeeRequest, QueryBaseFeeResponse>::default(),
+            ),
+        )
+        .await??
+        .into_inner();
+        parse_base_fee(&res.base_fee)
+    }
+
+    /// Simulates the provided array of messages and returns a fee in `denom` paying the
+    /// current base fee for the simulated gas with the default gas adjustment applied,
+    /// `denom` should be the evm denom of the chain. See `estimate_fee` for chains
+    /// without a fee market
+    pub async fn estimate_fee_from_base_fee(
+        &self,
+        messages: &[Msg],
+        denom: &str,
+        private_key: impl PrivateKey,
+    ) -> Result<Coin, CosmosGrpcError> {
+        let base_fee = self.get_base_fee().await?;
+        let fee = self.get_fee_info(messages, &[], private_key).await?;
+        let amount = base_fee
+            .checked_mul(&Uint256::from(fee.gas_limit))
+            .ok_or_else(|| {
+                CosmosGrpcError::BadResponse(format!("Base fee {base_fee} overflows"))
+            })?;
+        Ok(Coin {
+            amount,
+            denom: denom.to_string(),
+        })
+    }
+}
+
+fn parse_base_fee(base_fee: &str) -> Result<Uint256, CosmosGrpcError> {
+    if base_fee.is_empty() {
+        return Err(CosmosGrpcError::BadResponse(
+            "Base fee is disabled on this chain".to_string(),
+        ));
+    }
+    base_fee
+        .parse()
+        .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid base fee {base_fee} {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use prost::Message;
+
+    #[test]
+    fn test_parse_base_fee() {
+        // the wire format matches ethermint, a single string field
+        let encoded = QueryBaseFeeResponse {
+            base_fee: "1000000000".to_string(),
+        }
+        .encode_to_vec();
+        assert_eq!(encoded[0], 0x0a);
+        let decoded = QueryBaseFeeResponse::decode(encoded.as_slice()).unwrap();
+
+        assert_eq!(
+            parse_base_fee(&decoded.base_fee).unwrap(),
+            1_000_000_000u64.into()
+        );
+        assert!(parse_base_fee("").is_err());
+        assert!(parse_base_fee("-5").is_err());
+    }
+}
@@ -13,9 +13,13 @@ use cosmos_sdk_proto::cosmos::params::v1beta1::QueryParamsResponse;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::service_client::ServiceClient as TxServiceClient;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxRequest;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxResponse;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::GetTxsEventRequest;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::OrderBy;
 use cosmos_sdk_proto::tendermint::types::Block;
+use futures::stream::{FuturesOrdered, StreamExt};
 use std::time::Duration;
 use std::time::Instant;
+use std::time::SystemTime;
 use tokio::time::{sleep, timeout};
 
 /// This is the default block timeout, it's used when the user doesn't specify a timeout
@@ -27,11 +31,7 @@ impl Contact {
     /// Gets the current chain status, returns an enum taking into account the various possible states
     /// of the chain and the requesting full node. In the common case this provides the block number
     pub async fn get_chain_status(&self) -> Result<ChainStatus, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            TendermintServiceClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, TendermintServiceClient);
         let syncing = timeout(self.get_timeout(), grpc.get_syncing(GetSyncingRequest {}))
             .await??
             .into_inner();
@@ -74,11 +74,7 @@ impl Contact {
     /// Gets the latest block from the node, taking into account the possibility that the chain is halted
     /// and also the possibility that the node is syncing
     pub async fn get_latest_block(&self) -> Result<LatestBlock, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            TendermintServiceClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, TendermintServiceClient);
         let syncing = timeout(self.get_timeout(), grpc.get_syncing(GetSyncingRequest {}))
             .await??
             .into_inner()
@@ -103,13 +99,58 @@ impl Contact {
         }
     }
 
+    /// Gets the timestamp of the latest block, useful for time based logic such as unbonding
+    /// completion or IBC timeouts where the block height alone is not enough
+    pub async fn get_latest_block_time(&self) -> Result<SystemTime, CosmosGrpcError> {
+        match self.get_latest_block().await? {
+            LatestBlock::Latest { block } => block_time(&block),
+            LatestBlock::Syncing { .. } => Err(CosmosGrpcError::NodeNotSynced),
+            LatestBlock::WaitingToStart => Err(CosmosGrpcError::ChainNotRunning),
+        }
+    }
+
+    /// Estimates the average time between blocks by comparing the timestamp of the latest block
+    /// with the block `sample_blocks` before it, useful for converting a block height delta into
+    /// an approximate wall clock duration
+    pub async fn estimate_block_time(
+        &self,
+        sample_blocks: u64,
+    ) -> Result<Duration, CosmosGrpcError> {
+        let latest = match self.get_latest_block().await? {
+            LatestBlock::Latest { block } => block,
+            LatestBlock::Syncing { .. } => return Err(CosmosGrpcError::NodeNotSynced),
+            LatestBlock::WaitingToStart => return Err(CosmosGrpcError::ChainNotRunning),
+        };
+        let latest_height = match &latest.header {
+            Some(header) => header.height as u64,
+            None => {
+                return Err(CosmosGrpcError::BadResponse(
+                    "Null block header?".to_string(),
+                ))
+            }
+        };
+        if sample_blocks == 0 || sample_blocks >= latest_height {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Can not sample {sample_blocks} blocks at height {latest_height}"
+            )));
+        }
+        let past = match self.get_block(latest_height - sample_blocks).await? {
+            Some(block) => block,
+            None => {
+                return Err(CosmosGrpcError::BadResponse(format!(
+                    "Block {} not available",
+                    latest_height - sample_blocks
+                )))
+            }
+        };
+        average_block_time(block_time(&latest)?, block_time(&past)?, sample_blocks)
+    }
+
     /// Gets the specified block from the node, returns none if no block is available
+    /// blocks larger than 4MB fail with "message length too large" unless the decoding limit
+    /// is raised with `set_max_decoding_message_size`, this also applies to get_block_range
     pub async fn get_block(&self, block: u64) -> Result<Option<Block>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            TendermintServiceClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, TendermintServiceClient);
 
         let block = timeout(
             self.get_timeout(),
@@ -124,18 +165,13 @@ impl Contact {
 
     /// Gets the specified block range from the node, returning None if no block is available
     /// this is more efficient than querying individually since it uses a single grpc session
-    /// this could be made more efficient by distributing requests over several grpc sessions
-    /// once some minimum range requirement was met
+    /// see get_block_range_concurrent for a faster version that keeps several requests in flight
     pub async fn get_block_range(
         &self,
         start: u64,
         end: u64,
     ) -> Result<Vec<Option<Block>>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            TendermintServiceClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, TendermintServiceClient);
         let mut result = Vec::new();
         for i in start..end {
             let block = timeout(
@@ -150,6 +186,49 @@ impl Contact {
         Ok(result)
     }
 
+    /// Gets the specified block range from the node, returning None if no block is available.
+    /// Up to `concurrency` requests are kept in flight at once over the same grpc session, the
+    /// output is in the same order as get_block_range with missing blocks occupying their slot
+    pub async fn get_block_range_concurrent(
+        &self,
+        start: u64,
+        end: u64,
+        concurrency: usize,
+    ) -> Result<Vec<Option<Block>>, CosmosGrpcError> {
+        let grpc = grpc_client!(self, TendermintServiceClient);
+        let fetch = |height: u64| {
+            let mut grpc = grpc.clone();
+            let timeout_duration = self.get_timeout();
+            async move {
+                let block = timeout(
+                    timeout_duration,
+                    grpc.get_block_by_height(GetBlockByHeightRequest {
+                        height: height as i64,
+                    }),
+                )
+                .await??
+                .into_inner();
+                Ok::<_, CosmosGrpcError>(block.block)
+            }
+        };
+
+        let mut heights = start..end;
+        let mut in_flight = FuturesOrdered::new();
+        for height in heights.by_ref().take(concurrency.max(1)) {
+            in_flight.push_back(fetch(height));
+        }
+
+        let mut result = Vec::new();
+        while let Some(block) = in_flight.next().await {
+            result.push(block?);
+            if let Some(height) = heights.next() {
+                in_flight.push_back(fetch(height));
+            }
+        }
+
+        Ok(result)
+    }
+
     /// Queries the block params, including max block tx size and gas from the chain, useful for
     /// determining just how big a transaction can be before it will be rejected.
     /// This is extra useful because cosmos-sdk behaves very strangely when
@@ -180,11 +259,7 @@ impl Contact {
         subspace: impl ToString,
         key: impl ToString,
     ) -> Result<QueryParamsResponse, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            ParamsQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, ParamsQueryClient);
         let res = timeout(
             self.get_timeout(),
             grpc.params(QueryParamsRequest {
@@ -198,11 +273,7 @@ impl Contact {
 
     // Gets a transaction using it's hash value, TODO should fail if the transaction isn't found
     pub async fn get_tx_by_hash(&self, txhash: String) -> Result<GetTxResponse, CosmosGrpcError> {
-        let mut txrpc = timeout(
-            self.get_timeout(),
-            TxServiceClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut txrpc = grpc_client!(self, TxServiceClient);
         let res = timeout(
             self.get_timeout(),
             txrpc.get_tx(GetTxRequest { hash: txhash }),
@@ -212,6 +283,57 @@ impl Contact {
         Ok(res)
     }
 
+    /// Searches for transactions matching all of the provided `events`, for example
+    /// `message.action='/cosmos.bank.v1beta1.MsgSend'` and `transfer.recipient='cosmos1...'`.
+    /// Results are paginated, `page` starts at 1 and `limit` is the number of results per page,
+    /// use `TxSearchResult::has_next_page` to determine if there are more pages to request
+    pub async fn get_txs_by_events(
+        &self,
+        events: Vec<String>,
+        page: u64,
+        limit: u64,
+    ) -> Result<TxSearchResult, CosmosGrpcError> {
+        let mut txrpc = grpc_client!(self, TxServiceClient);
+        // used to avoid the deprecation warning on the pagination field
+        #[allow(deprecated)]
+        let request = GetTxsEventRequest {
+            events,
+            pagination: None,
+            order_by: OrderBy::Asc.into(),
+            page,
+            limit,
+        };
+        let res = timeout(self.get_timeout(), txrpc.get_txs_event(request))
+            .await??
+            .into_inner();
+        Ok(TxSearchResult {
+            tx_responses: res.tx_responses,
+            total: res.total,
+            page,
+            limit,
+        })
+    }
+
+    /// Returns the chain-id of the chain, this is read from the latest block on first use
+    /// and then cached, the cache is shared between all clones of this Contact. If a chain-id
+    /// was pinned with `set_chain_id` that is returned instead
+    pub async fn get_chain_id(&self) -> Result<String, CosmosGrpcError> {
+        let pinned = self.pinned_chain_id.lock().unwrap().clone();
+        let cached = pinned.or_else(|| self.chain_id.lock().unwrap().clone());
+        if let Some(chain_id) = cached {
+            return Ok(chain_id);
+        }
+        match self.get_latest_block().await? {
+            LatestBlock::Latest { block } | LatestBlock::Syncing { block } => match block.header {
+                Some(header) => Ok(self.resolve_chain_id(header.chain_id)),
+                None => Err(CosmosGrpcError::BadResponse(
+                    "Null block header?".to_string(),
+                )),
+            },
+            LatestBlock::WaitingToStart => Err(CosmosGrpcError::ChainNotRunning),
+        }
+    }
+
     /// Grabs an up to date MessageArgs structure for an address,
     /// provided a fee value to insert into the structure. The goal of
     /// this function is to be very minimal and make a lot of choices for
@@ -229,14 +351,14 @@ impl Contact {
         match latest_block {
             LatestBlock::Latest { block } => {
                 if let Some(header) = block.header {
+                    let chain_id = self.resolve_chain_id(header.chain_id);
                     Ok(MessageArgs {
                         sequence: account_info.sequence,
                         account_number: account_info.account_number,
-                        chain_id: header.chain_id,
+                        chain_id,
                         fee,
                         tip: None,
-                        timeout_height: header.height as u64
-                            + timeout_block.unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_BLOCKS),
+                        timeout_height: timeout_height(header.height as u64, timeout_block),
                     })
                 } else {
                     Err(CosmosGrpcError::BadResponse(
@@ -275,6 +397,61 @@ impl Contact {
         }
         Err(CosmosGrpcError::NoBlockProduced { time: timeout })
     }
+
+    /// Waits until the chain reaches at least the `target` block height, useful for tests
+    /// and for coordinating actions such as upgrades that happen at a specific height
+    pub async fn wait_for_height(
+        &self,
+        target: u64,
+        timeout: Duration,
+    ) -> Result<(), CosmosGrpcError> {
+        let start = Instant::now();
+        while Instant::now() - start < timeout {
+            match self.get_chain_status().await {
+                Ok(ChainStatus::Moving { block_height }) => {
+                    if block_height >= target {
+                        return Ok(());
+                    }
+                }
+                Ok(ChainStatus::Syncing) => return Err(CosmosGrpcError::NodeNotSynced),
+                Ok(ChainStatus::WaitingToStart) => return Err(CosmosGrpcError::ChainNotRunning),
+                // we don't want a single error to exit this loop early
+                Err(_) => {}
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        Err(CosmosGrpcError::NoBlockProduced { time: timeout })
+    }
+}
+
+/// Computes the height after which a transaction sent at `current_height` is no longer valid,
+/// `block_timeout` blocks later or DEFAULT_TRANSACTION_TIMEOUT_BLOCKS if it is None
+fn timeout_height(current_height: u64, block_timeout: Option<u64>) -> u64 {
+    current_height.saturating_add(block_timeout.unwrap_or(DEFAULT_TRANSACTION_TIMEOUT_BLOCKS))
+}
+
+/// Reads the header timestamp of a block
+fn block_time(block: &Block) -> Result<SystemTime, CosmosGrpcError> {
+    let time = block
+        .header
+        .as_ref()
+        .and_then(|header| header.time)
+        .ok_or_else(|| CosmosGrpcError::BadResponse("Null block time?".to_string()))?;
+    SystemTime::try_from(time).map_err(|e| CosmosGrpcError::BadResponse(e.to_string()))
+}
+
+/// Divides the time elapsed between two block timestamps by the number of blocks between them
+fn average_block_time(
+    newer: SystemTime,
+    older: SystemTime,
+    blocks: u64,
+) -> Result<Duration, CosmosGrpcError> {
+    let elapsed = newer
+        .duration_since(older)
+        .map_err(|e| CosmosGrpcError::BadResponse(e.to_string()))?;
+    Ok(Duration::from_nanos(
+        (elapsed.as_nanos() / blocks.max(1) as u128) as u64,
+    ))
 }
 
 /// One off struct for deserialization of the BlockParams struct
@@ -293,3 +470,49 @@ impl From<BlockParamsJson> for BlockParams {
         BlockParams { max_bytes, max_gas }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::tendermint::types::Header;
+
+    #[test]
+    fn test_timeout_height() {
+        assert_eq!(
+            timeout_height(1000, None),
+            1000 + DEFAULT_TRANSACTION_TIMEOUT_BLOCKS
+        );
+        assert_eq!(timeout_height(1000, Some(5)), 1005);
+        assert_eq!(timeout_height(1000, Some(0)), 1000);
+        assert_eq!(timeout_height(u64::MAX, Some(5)), u64::MAX);
+    }
+
+    #[test]
+    fn test_average_block_time() {
+        let older = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let newer = older + Duration::from_secs(600);
+        assert_eq!(
+            average_block_time(newer, older, 100).unwrap(),
+            Duration::from_secs(6)
+        );
+        assert!(average_block_time(older, newer, 100).is_err());
+    }
+
+    #[test]
+    fn test_block_time() {
+        let mut block = Block::default();
+        assert!(block_time(&block).is_err());
+
+        block.header = Some(Header {
+            time: Some(prost_types::Timestamp {
+                seconds: 1_700_000_000,
+                nanos: 500,
+            }),
+            ..Default::default()
+        });
+        assert_eq!(
+            block_time(&block).unwrap(),
+            SystemTime::UNIX_EPOCH + Duration::new(1_700_000_000, 500)
+        );
+    }
+}
@@ -1,50 +1,203 @@
 //! Contains utility functions for interacting with and modifying Cosmos validator staking status
 
 use super::send::TransactionResponse;
-use super::type_urls::{PARAMETER_CHANGE_PROPOSAL_TYPE_URL, SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL};
+use super::type_urls::{
+    COMMUNITY_POOL_SPEND_PROPOSAL_TYPE_URL, PARAMETER_CHANGE_PROPOSAL_TYPE_URL,
+    SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL,
+};
 use super::PAGE;
-use crate::client::type_urls::{MSG_SUBMIT_PROPOSAL_TYPE_URL, MSG_VOTE_TYPE_URL};
+use crate::client::type_urls::{
+    MSG_DEPOSIT_TYPE_URL, MSG_DEPOSIT_V1_TYPE_URL, MSG_SUBMIT_PROPOSAL_TYPE_URL, MSG_VOTE_TYPE_URL,
+    MSG_VOTE_WEIGHTED_TYPE_URL,
+};
+use crate::decimal::Decimal;
 use crate::error::CosmosGrpcError;
-use crate::utils::encode_any;
+use crate::utils::{encode_any, find_event_attribute};
+use crate::Address;
 use crate::Coin;
 use crate::Contact;
 use crate::Msg;
 use crate::PrivateKey;
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
+use cosmos_sdk_proto::cosmos::distribution::v1beta1::CommunityPoolSpendProposal;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::query_client::QueryClient as GovQueryClient;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::Deposit;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgDeposit;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::MsgVote;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::Proposal;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::ProposalStatus;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryDepositsRequest;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryProposalRequest;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryProposalsRequest;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryProposalsResponse;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryTallyResultRequest;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::QueryVotesRequest;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::TallyResult;
+use cosmos_sdk_proto::cosmos::gov::v1beta1::Vote;
 use cosmos_sdk_proto::cosmos::gov::v1beta1::VoteOption;
 use cosmos_sdk_proto::cosmos::params::v1beta1::ParameterChangeProposal;
 use cosmos_sdk_proto::cosmos::upgrade::v1beta1::SoftwareUpgradeProposal;
 use prost_types::Any;
 use std::time::Duration;
 use tokio::time::timeout;
+use tonic::Code as GrpcCode;
 
 #[cfg(feature = "althea")]
 use super::type_urls::{REGISTER_COIN_PROPOSAL_TYPE_URL, REGISTER_ERC20_PROPOSAL_TYPE_URL};
 #[cfg(feature = "althea")]
 use althea_proto::canto::erc20::v1::{RegisterCoinProposal, RegisterErc20Proposal};
 
+/// The gov v1 MsgDeposit added in Cosmos SDK 0.46, not included in cosmos-sdk-proto
+#[derive(Clone, PartialEq, prost::Message)]
+struct MsgDepositV1 {
+    #[prost(uint64, tag = "1")]
+    proposal_id: u64,
+    #[prost(string, tag = "2")]
+    depositor: String,
+    #[prost(message, repeated, tag = "3")]
+    amount: Vec<ProtoCoin>,
+}
+
+/// The gov v1 MsgVoteWeighted, not included in cosmos-sdk-proto
+#[derive(Clone, PartialEq, prost::Message)]
+struct MsgVoteWeighted {
+    #[prost(uint64, tag = "1")]
+    proposal_id: u64,
+    #[prost(string, tag = "2")]
+    voter: String,
+    #[prost(message, repeated, tag = "3")]
+    options: Vec<WeightedVoteOption>,
+    #[prost(string, tag = "4")]
+    metadata: String,
+}
+
+/// A single option and its weight in a gov v1 MsgVoteWeighted
+#[derive(Clone, PartialEq, prost::Message)]
+struct WeightedVoteOption {
+    #[prost(int32, tag = "1")]
+    option: i32,
+    #[prost(string, tag = "2")]
+    weight: String,
+}
+
 impl Contact {
     /// Gets a list of governance proposals, user provides filter items
     pub async fn get_governance_proposals(
         &self,
         filters: QueryProposalsRequest,
     ) -> Result<QueryProposalsResponse, CosmosGrpcError> {
-        let mut grpc = timeout(
+        let mut grpc = grpc_client!(self, GovQueryClient);
+        let res = timeout(
             self.get_timeout(),
-            GovQueryClient::connect(self.url.clone()),
+            grpc.proposals(self.query_request(filters)),
         )
-        .await??;
-        let res = timeout(self.get_timeout(), grpc.proposals(filters))
-            .await??
-            .into_inner();
+        .await??
+        .into_inner();
         Ok(res)
     }
 
+    /// Gets a single governance proposal by id, returns None if no proposal with
+    /// the given id exists
+    pub async fn get_governance_proposal(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<Proposal>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, GovQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.proposal(self.query_request(QueryProposalRequest { proposal_id })),
+        )
+        .await?;
+        match res {
+            Ok(res) => Ok(res.into_inner().proposal),
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+
+    /// Gets the current vote tally for a governance proposal, for proposals still in the
+    /// voting period this is a live count, otherwise it is the final tally
+    pub async fn get_proposal_tally(
+        &self,
+        proposal_id: u64,
+    ) -> Result<TallyResult, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, GovQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.tally_result(self.query_request(QueryTallyResultRequest { proposal_id })),
+        )
+        .await??
+        .into_inner();
+        match res.tally {
+            Some(tally) => Ok(tally),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No tally in response".to_string(),
+            )),
+        }
+    }
+
+    /// Gets every deposit made on a governance proposal
+    pub async fn get_proposal_deposits(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Vec<Deposit>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, GovQueryClient);
+        let mut page = PAGE;
+        let mut deposits = Vec::new();
+
+        loop {
+            let query = QueryDepositsRequest {
+                proposal_id,
+                pagination: page.clone(),
+            };
+            let res = timeout(self.get_timeout(), grpc.deposits(self.query_request(query)))
+                .await??
+                .into_inner();
+
+            deposits.extend(res.deposits);
+            match (res.pagination, page.as_mut()) {
+                (Some(page_response), Some(page)) if !page_response.next_key.is_empty() => {
+                    page.key = page_response.next_key;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(deposits)
+    }
+
+    /// Gets every vote cast on a governance proposal, note that votes are pruned
+    /// once a proposal leaves the voting period
+    pub async fn get_proposal_votes(&self, proposal_id: u64) -> Result<Vec<Vote>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, GovQueryClient);
+        let mut page = PAGE;
+        let mut votes = Vec::new();
+
+        loop {
+            let query = QueryVotesRequest {
+                proposal_id,
+                pagination: page.clone(),
+            };
+            let res = timeout(self.get_timeout(), grpc.votes(self.query_request(query)))
+                .await??
+                .into_inner();
+
+            votes.extend(res.votes);
+            match (res.pagination, page.as_mut()) {
+                (Some(page_response), Some(page)) if !page_response.next_key.is_empty() => {
+                    page.key = page_response.next_key;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(votes)
+    }
+
     /// Gets a list of all active governance proposals currently in the voting period
     pub async fn get_governance_proposals_in_voting_period(
         &self,
@@ -125,6 +278,76 @@ impl Contact {
             .await
     }
 
+    /// Votes on a governance proposal splitting the voting power of the key across several
+    /// options using the gov v1 MsgVoteWeighted. Each weight must be positive and the weights
+    /// must sum to exactly 1
+    pub async fn vote_weighted_on_gov_proposal(
+        &self,
+        proposal_id: u64,
+        options: Vec<(VoteOption, Decimal)>,
+        metadata: String,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let options = build_weighted_vote_options(options)?;
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let vote = MsgVoteWeighted {
+            proposal_id,
+            voter: our_address.to_string(),
+            options,
+            metadata,
+        };
+
+        let msg = Msg::new(MSG_VOTE_WEIGHTED_TYPE_URL, vote);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Adds a deposit to a governance proposal in the deposit period using the gov v1 MsgDeposit,
+    /// once the minimum deposit is reached the proposal enters the voting period
+    pub async fn deposit_on_gov_proposal(
+        &self,
+        proposal_id: u64,
+        amount: Vec<Coin>,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let deposit = MsgDepositV1 {
+            proposal_id,
+            depositor: our_address.to_string(),
+            amount: amount.into_iter().map(|c| c.into()).collect(),
+        };
+
+        let msg = Msg::new(MSG_DEPOSIT_V1_TYPE_URL, deposit);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Adds a deposit to a governance proposal in the deposit period using the legacy
+    /// gov v1beta1 MsgDeposit, for chains that do not support gov v1
+    pub async fn deposit_on_legacy_gov_proposal(
+        &self,
+        proposal_id: u64,
+        amount: Vec<Coin>,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let deposit = MsgDeposit {
+            proposal_id,
+            depositor: our_address.to_string(),
+            amount: amount.into_iter().map(|c| c.into()).collect(),
+        };
+
+        let msg = Msg::new(MSG_DEPOSIT_TYPE_URL, deposit);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
     /// Provides an interface for submitting governance proposals
     pub async fn create_gov_proposal(
         &self,
@@ -146,6 +369,23 @@ impl Contact {
             .await
     }
 
+    /// Submits a governance proposal like `create_gov_proposal` and returns the id of the new
+    /// proposal, parsed from the `submit_proposal` event of the transaction. Since the events
+    /// are only available once the transaction is in a block this always waits for inclusion
+    pub async fn create_gov_proposal_get_id(
+        &self,
+        content: Any,
+        deposit: Coin,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Duration,
+    ) -> Result<u64, CosmosGrpcError> {
+        let res = self
+            .create_gov_proposal(content, deposit, fee, private_key, Some(wait_timeout))
+            .await?;
+        proposal_id_from_tx(&res.into())
+    }
+
     /// Encodes and submits a proposal to change bridge parameters
     pub async fn submit_parameter_change_proposal(
         &self,
@@ -175,6 +415,60 @@ impl Contact {
         self.create_gov_proposal(any, deposit, fee, key, wait_timeout)
             .await
     }
+
+    /// Encodes and submits a proposal to pay `amount` from the community pool to `recipient`
+    #[allow(clippy::too_many_arguments)]
+    pub async fn submit_community_pool_spend_proposal(
+        &self,
+        recipient: Address,
+        amount: Vec<Coin>,
+        title: String,
+        description: String,
+        deposit: Coin,
+        fee: Coin,
+        key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let proposal = CommunityPoolSpendProposal {
+            title,
+            description,
+            recipient: recipient.to_string(),
+            amount: amount.into_iter().map(|c| c.into()).collect(),
+        };
+        // encode as a generic proposal
+        let any = encode_any(proposal, COMMUNITY_POOL_SPEND_PROPOSAL_TYPE_URL.to_string());
+        self.create_gov_proposal(any, deposit, fee, key, wait_timeout)
+            .await
+    }
+}
+
+/// Validates the provided weights and converts them into WeightedVoteOptions
+fn build_weighted_vote_options(
+    options: Vec<(VoteOption, Decimal)>,
+) -> Result<Vec<WeightedVoteOption>, CosmosGrpcError> {
+    let zero = Decimal::from(0u8);
+    let mut total = zero;
+    let mut out = Vec::new();
+    for (option, weight) in options {
+        if weight <= zero {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Vote weight {weight} must be positive"
+            )));
+        }
+        total = total
+            .checked_add(weight)
+            .map_err(|e| CosmosGrpcError::BadInput(format!("Invalid vote weights {e}")))?;
+        out.push(WeightedVoteOption {
+            option: option.into(),
+            weight: weight.to_string(),
+        });
+    }
+    if total != Decimal::from(1u8) {
+        return Err(CosmosGrpcError::BadInput(format!(
+            "Vote weights sum to {total} not 1"
+        )));
+    }
+    Ok(out)
 }
 
 #[cfg(feature = "althea")]
@@ -209,3 +503,70 @@ impl Contact {
             .await
     }
 }
+
+/// Parses the id of a newly created proposal from the `submit_proposal` event of the
+/// transaction that created it
+fn proposal_id_from_tx(tx: &TxResponse) -> Result<u64, CosmosGrpcError> {
+    match find_event_attribute(tx, "submit_proposal", "proposal_id") {
+        Some(id) => id.parse().map_err(|e| {
+            CosmosGrpcError::BadResponse(format!("Invalid proposal_id {id} in tx events {e:?}"))
+        }),
+        None => Err(CosmosGrpcError::BadResponse(
+            "No proposal_id in tx events".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proposal_id_from_tx() {
+        use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{
+            AbciMessageLog, Attribute, StringEvent,
+        };
+
+        let tx = |value: &str| TxResponse {
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![StringEvent {
+                    r#type: "submit_proposal".to_string(),
+                    attributes: vec![Attribute {
+                        key: "proposal_id".to_string(),
+                        value: value.to_string(),
+                    }],
+                }],
+            }],
+            ..Default::default()
+        };
+        assert_eq!(proposal_id_from_tx(&tx("12")).unwrap(), 12);
+        assert!(proposal_id_from_tx(&tx("twelve")).is_err());
+        assert!(proposal_id_from_tx(&TxResponse::default()).is_err());
+    }
+
+    #[test]
+    fn test_weighted_vote_options() {
+        let half: Decimal = "0.500000000000000000".parse().unwrap();
+        let quarter: Decimal = "0.250000000000000000".parse().unwrap();
+
+        let options = build_weighted_vote_options(vec![
+            (VoteOption::Yes, half),
+            (VoteOption::No, quarter),
+            (VoteOption::Abstain, quarter),
+        ])
+        .unwrap();
+        assert_eq!(options.len(), 3);
+        assert_eq!(options[0].weight, "0.500000000000000000");
+        assert_eq!(options[0].option, i32::from(VoteOption::Yes));
+
+        assert!(build_weighted_vote_options(vec![(VoteOption::Yes, half)]).is_err());
+        assert!(build_weighted_vote_options(vec![]).is_err());
+        assert!(build_weighted_vote_options(vec![
+            (VoteOption::Yes, Decimal::from(2u8)),
+            (VoteOption::No, Decimal::from(-1i8)),
+        ])
+        .is_err());
+    }
+}
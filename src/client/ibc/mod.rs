@@ -0,0 +1,365 @@
+//! Contains utility functions for querying the state of ibc clients and channels
+
+use super::types::{IbcPacketId, IbcTransferStatus};
+use super::PAGE;
+use crate::error::CosmosGrpcError;
+use crate::utils::{find_event_attribute, tx_events};
+use crate::Contact;
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmos_sdk_proto::ibc::core::channel::v1::query_client::QueryClient as IbcChannelQueryClient;
+use cosmos_sdk_proto::ibc::core::channel::v1::{
+    Channel, IdentifiedChannel, QueryChannelRequest, QueryChannelsRequest,
+    QueryPacketAcknowledgementRequest, QueryPacketCommitmentRequest,
+};
+use cosmos_sdk_proto::ibc::core::client::v1::query_client::QueryClient as IbcClientQueryClient;
+use cosmos_sdk_proto::ibc::core::client::v1::QueryClientStateRequest;
+use prost_types::Any;
+use std::time::{Duration, Instant};
+use tokio::time::{sleep, timeout};
+use tonic::Code as GrpcCode;
+
+impl Contact {
+    /// Gets all the ibc channels on this chain along with their port and channel ids
+    pub async fn query_ibc_channels(&self) -> Result<Vec<IdentifiedChannel>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, IbcChannelQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.channels(self.query_request(QueryChannelsRequest { pagination: PAGE })),
+        )
+        .await??
+        .into_inner();
+        Ok(res.channels)
+    }
+
+    /// Gets a single ibc channel, returns None if the channel does not exist. The state
+    /// of the channel should be checked to be OPEN before sending an ibc transfer over it
+    pub async fn query_ibc_channel(
+        &self,
+        port_id: impl ToString,
+        channel_id: impl ToString,
+    ) -> Result<Option<Channel>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, IbcChannelQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.channel(self.query_request(QueryChannelRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+            })),
+        )
+        .await?;
+        match res {
+            Ok(res) => Ok(res.into_inner().channel),
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+
+    /// Gets the state of an ibc light client, returns None if the client does not exist.
+    /// The state is returned encoded, the type url identifies the client type, for example
+    /// "/ibc.lightclients.tendermint.v1.ClientState"
+    pub async fn query_ibc_client_state(
+        &self,
+        client_id: impl ToString,
+    ) -> Result<Option<Any>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, IbcClientQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.client_state(self.query_request(QueryClientStateRequest {
+                client_id: client_id.to_string(),
+            })),
+        )
+        .await?;
+        match res {
+            Ok(res) => Ok(res.into_inner().client_state),
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+
+    /// Gets the commitment of a packet sent from this chain, returns None once the packet has
+    /// been acknowledged or timed out, at which point the commitment is deleted
+    pub async fn query_packet_commitment(
+        &self,
+        port_id: impl ToString,
+        channel_id: impl ToString,
+        sequence: u64,
+    ) -> Result<Option<Vec<u8>>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, IbcChannelQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.packet_commitment(self.query_request(QueryPacketCommitmentRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                sequence,
+            })),
+        )
+        .await?;
+        match res {
+            Ok(res) => {
+                let commitment = res.into_inner().commitment;
+                Ok(if commitment.is_empty() {
+                    None
+                } else {
+                    Some(commitment)
+                })
+            }
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+
+    /// Gets the acknowledgement written by this chain for a packet it received, this should be
+    /// called on the destination chain with the destination port and channel of the packet.
+    /// Returns None if the packet has not been received
+    pub async fn query_packet_acknowledgement(
+        &self,
+        port_id: impl ToString,
+        channel_id: impl ToString,
+        sequence: u64,
+    ) -> Result<Option<Vec<u8>>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, IbcChannelQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.packet_acknowledgement(self.query_request(QueryPacketAcknowledgementRequest {
+                port_id: port_id.to_string(),
+                channel_id: channel_id.to_string(),
+                sequence,
+            })),
+        )
+        .await?;
+        match res {
+            Ok(res) => {
+                let ack = res.into_inner().acknowledgement;
+                Ok(if ack.is_empty() { None } else { Some(ack) })
+            }
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+
+    /// Waits for the ibc transfer sent by `tx` to be either acknowledged or timed out, by polling
+    /// the packet commitment on this (the sending) chain until it is removed and then searching
+    /// for the transaction that removed it. The acknowledgement result is read from the events
+    /// of the ics20 transfer module, so this only supports transfers. Returns TimeoutError if
+    /// the packet is still pending after `timeout`, and BadResponse if the commitment was
+    /// removed but the transaction that removed it can not be found, for example because the
+    /// node does not index transactions. `tx` may be either the TransactionResponse returned
+    /// when sending or a raw TxResponse
+    pub async fn track_ibc_transfer(
+        &self,
+        tx: impl Into<TxResponse>,
+        timeout: Duration,
+    ) -> Result<IbcTransferStatus, CosmosGrpcError> {
+        let tx: TxResponse = tx.into();
+        let packet = get_sent_packet(&tx).ok_or_else(|| {
+            CosmosGrpcError::BadInput(format!("No send_packet event in tx {}", tx.txhash))
+        })?;
+        let start = Instant::now();
+        let mut commitment_removed = false;
+        while Instant::now() - start < timeout {
+            if !commitment_removed {
+                commitment_removed = self
+                    .query_packet_commitment(&packet.port_id, &packet.channel_id, packet.sequence)
+                    .await?
+                    .is_none();
+            }
+            // the removing transaction may not be indexed yet, so keep searching until timeout
+            if commitment_removed {
+                if self
+                    .find_packet_tx("timeout_packet", &packet)
+                    .await?
+                    .is_some()
+                {
+                    return Ok(IbcTransferStatus::TimedOut);
+                }
+                if let Some(ack_tx) = self.find_packet_tx("acknowledge_packet", &packet).await? {
+                    return get_transfer_ack_status(&ack_tx, &packet).ok_or_else(|| {
+                        CosmosGrpcError::BadResponse(format!(
+                            "No transfer acknowledgement result in tx {}",
+                            ack_tx.txhash
+                        ))
+                    });
+                }
+            }
+            sleep(Duration::from_secs(1)).await;
+        }
+        if commitment_removed {
+            Err(CosmosGrpcError::BadResponse(format!(
+                "Commitment for packet {} on {} removed but no acknowledgement or timeout tx found",
+                packet.sequence, packet.channel_id
+            )))
+        } else {
+            Err(CosmosGrpcError::TimeoutError)
+        }
+    }
+
+    /// Searches for the transaction that emitted an `event_type` event for `packet`
+    async fn find_packet_tx(
+        &self,
+        event_type: &str,
+        packet: &IbcPacketId,
+    ) -> Result<Option<TxResponse>, CosmosGrpcError> {
+        let res = self
+            .get_txs_by_events(
+                vec![
+                    format!("{event_type}.packet_sequence='{}'", packet.sequence),
+                    format!("{event_type}.packet_src_port='{}'", packet.port_id),
+                    format!("{event_type}.packet_src_channel='{}'", packet.channel_id),
+                ],
+                1,
+                1,
+            )
+            .await?;
+        Ok(res.tx_responses.into_iter().next())
+    }
+}
+
+/// Finds the ibc packet sent by a transaction from its send_packet event, if the transaction
+/// sent several packets the first is returned
+pub fn get_sent_packet(tx: &TxResponse) -> Option<IbcPacketId> {
+    Some(IbcPacketId {
+        port_id: find_event_attribute(tx, "send_packet", "packet_src_port")?,
+        channel_id: find_event_attribute(tx, "send_packet", "packet_src_channel")?,
+        sequence: find_event_attribute(tx, "send_packet", "packet_sequence")?
+            .parse()
+            .ok()?,
+    })
+}
+
+/// Reads the result of the acknowledgement of the transfer `packet` from the transaction that
+/// relayed it. Relayers often acknowledge several packets in one transaction, the transfer
+/// module emits the result as a `fungible_token_packet` event with either a `success` or an
+/// `error` attribute after the core `acknowledge_packet` event of each packet
+fn get_transfer_ack_status(tx: &TxResponse, packet: &IbcPacketId) -> Option<IbcTransferStatus> {
+    let sequence = packet.sequence.to_string();
+    let mut events = tx_events(tx).into_iter();
+    events.find(|e| {
+        e.event_type == "acknowledge_packet"
+            && e.get("packet_sequence").as_deref() == Some(sequence.as_str())
+            && e.get("packet_src_port").as_deref() == Some(packet.port_id.as_str())
+            && e.get("packet_src_channel").as_deref() == Some(packet.channel_id.as_str())
+    })?;
+    for event in events {
+        match event.event_type.as_str() {
+            // the next packet, no result was emitted for ours
+            "acknowledge_packet" => return None,
+            "fungible_token_packet" => {
+                if event.get("error").is_some() {
+                    return Some(IbcTransferStatus::ErrorAck);
+                } else if event.get("success").is_some() {
+                    return Some(IbcTransferStatus::Success);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{AbciMessageLog, Attribute, StringEvent};
+
+    #[test]
+    fn test_get_sent_packet() {
+        let attribute = |key: &str, value: &str| Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        };
+        let tx = TxResponse {
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![
+                    StringEvent {
+                        r#type: "transfer".to_string(),
+                        attributes: vec![attribute("amount", "100uatom")],
+                    },
+                    StringEvent {
+                        r#type: "send_packet".to_string(),
+                        attributes: vec![
+                            attribute("packet_sequence", "42"),
+                            attribute("packet_src_port", "transfer"),
+                            attribute("packet_src_channel", "channel-7"),
+                        ],
+                    },
+                ],
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_sent_packet(&tx),
+            Some(IbcPacketId {
+                port_id: "transfer".to_string(),
+                channel_id: "channel-7".to_string(),
+                sequence: 42,
+            })
+        );
+        assert_eq!(get_sent_packet(&TxResponse::default()), None);
+    }
+
+    #[test]
+    fn test_get_transfer_ack_status() {
+        use cosmos_sdk_proto::tendermint::abci::{Event, EventAttribute};
+
+        let event = |event_type: &str, attributes: &[(&str, &str)]| Event {
+            r#type: event_type.to_string(),
+            attributes: attributes
+                .iter()
+                .map(|(key, value)| EventAttribute {
+                    key: (*key).into(),
+                    value: (*value).into(),
+                    index: true,
+                })
+                .collect(),
+        };
+        let ack = |sequence: &str| {
+            event(
+                "acknowledge_packet",
+                &[
+                    ("packet_sequence", sequence),
+                    ("packet_src_port", "transfer"),
+                    ("packet_src_channel", "channel-7"),
+                ],
+            )
+        };
+        let packet = |sequence: u64| IbcPacketId {
+            port_id: "transfer".to_string(),
+            channel_id: "channel-7".to_string(),
+            sequence,
+        };
+        // a relayer acknowledging two packets in one transaction
+        let tx = TxResponse {
+            events: vec![
+                ack("1"),
+                event("fungible_token_packet", &[("amount", "100")]),
+                event("fungible_token_packet", &[("success", "\u{1}")]),
+                ack("2"),
+                event("fungible_token_packet", &[("amount", "100")]),
+                event("fungible_token_packet", &[("error", "invalid receiver")]),
+                ack("3"),
+            ],
+            ..Default::default()
+        };
+        assert_eq!(
+            get_transfer_ack_status(&tx, &packet(1)),
+            Some(IbcTransferStatus::Success)
+        );
+        assert_eq!(
+            get_transfer_ack_status(&tx, &packet(2)),
+            Some(IbcTransferStatus::ErrorAck)
+        );
+        // not a transfer packet
+        assert_eq!(get_transfer_ack_status(&tx, &packet(3)), None);
+        assert_eq!(get_transfer_ack_status(&tx, &packet(4)), None);
+    }
+}
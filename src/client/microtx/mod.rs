@@ -0,0 +1,91 @@
+//! Contains utility functions for querying the Althea microtx module, which is used by
+//! routers to pay each other for internet service
+
+use crate::client::type_urls::MSG_MICROTX_TYPE_URL;
+use crate::error::CosmosGrpcError;
+use crate::{Coin, Contact, Msg, PrivateKey};
+use althea_proto::althea::microtx::v1::query_client::QueryClient as MicrotxQueryClient;
+use althea_proto::althea::microtx::v1::{MsgMicrotx, Params as MicrotxParams, QueryParamsRequest};
+use num256::Uint256;
+use num_traits::CheckedMul;
+use tokio::time::timeout;
+
+impl Contact {
+    /// Gets the microtx module parameters, including the fee in basis points charged on
+    /// the amount of every microtx
+    pub async fn get_microtx_params(&self) -> Result<MicrotxParams, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, MicrotxQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.params(self.query_request(QueryParamsRequest {})),
+        )
+        .await??
+        .into_inner();
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No microtx params returned".to_string(),
+            )),
+        }
+    }
+
+    /// Estimates the total cost of sending `coin` with `send_microtx`, returning the gas fee,
+    /// paid in the native token of the chain (the mint denom) at the current base fee, and the
+    /// protocol fee charged by the microtx module on the amount, paid in the denom of `coin`.
+    /// The sender needs both on top of `coin`
+    pub async fn estimate_microtx_total_cost(
+        &self,
+        coin: Coin,
+        private_key: impl PrivateKey,
+    ) -> Result<(Coin, Coin), CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let params = self.get_microtx_params().await?;
+        let fee_denom = self.get_mint_denom().await?;
+        let protocol_fee = Coin {
+            amount: microtx_fee(coin.amount, params.microtx_fee_basis_points)?,
+            denom: coin.denom.clone(),
+        };
+
+        // the receiver does not change the gas used, so simulate sending to ourselves
+        let msg = Msg::new(
+            MSG_MICROTX_TYPE_URL,
+            MsgMicrotx {
+                sender: our_address.to_string(),
+                receiver: our_address.to_string(),
+                amount: Some(coin.into()),
+            },
+        );
+        let gas_fee = self
+            .estimate_fee_from_base_fee(&[msg], &fee_denom, private_key)
+            .await?;
+        Ok((gas_fee, protocol_fee))
+    }
+}
+
+/// Computes the fee the microtx module charges on `amount`, rounded down like the module
+fn microtx_fee(amount: Uint256, basis_points: u64) -> Result<Uint256, CosmosGrpcError> {
+    let fee = amount
+        .checked_mul(&Uint256::from(basis_points))
+        .ok_or_else(|| CosmosGrpcError::BadInput(format!("Microtx amount {amount} overflows")))?;
+    Ok(fee / 10_000u16.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Bounded;
+
+    #[test]
+    fn test_microtx_fee() {
+        assert_eq!(
+            microtx_fee(1_000_000u32.into(), 10).unwrap(),
+            1_000u32.into()
+        );
+        assert_eq!(microtx_fee(999u32.into(), 10).unwrap(), 0u8.into());
+        assert_eq!(microtx_fee(1_000_000u32.into(), 0).unwrap(), 0u8.into());
+        assert!(matches!(
+            microtx_fee(Uint256::max_value(), 10),
+            Err(CosmosGrpcError::BadInput(_))
+        ));
+    }
+}
@@ -1,5 +1,6 @@
 //! Contains utility functions for interacting with the Cosmos sdk mint module
 
+use crate::decimal::{Decimal, WideDecimal};
 use crate::error::CosmosGrpcError;
 use crate::Contact;
 use cosmos_sdk_proto::cosmos::mint::v1beta1::query_client::QueryClient as MintQueryClient;
@@ -7,6 +8,7 @@ use cosmos_sdk_proto::cosmos::mint::v1beta1::{
     Params as MintParms, QueryAnnualProvisionsRequest, QueryInflationRequest,
     QueryParamsRequest as QueryMintParamsRequest,
 };
+use num256::Uint256;
 use tokio::time::timeout;
 
 /// When a dec is returned in the vec format and decoded as a utf8 string it will be a whole number
@@ -16,69 +18,140 @@ const DEC_MANTISSA: f64 = 1_000_000_000_000_000_000.0;
 impl Contact {
     /// Returns the mint denom, or the native token on the chain
     pub async fn get_mint_denom(&self) -> Result<String, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            MintQueryClient::connect(self.url.clone()),
-        )
-        .await??;
-
-        let res = timeout(self.get_timeout(), grpc.params(QueryMintParamsRequest {}))
-            .await??
-            .into_inner();
-
-        Ok(res.params.unwrap().mint_denom)
+        Ok(self.get_mint_params().await?.mint_denom)
     }
 
     /// Returns the mint module parameters
     pub async fn get_mint_params(&self) -> Result<MintParms, CosmosGrpcError> {
-        let mut grpc = timeout(
+        let mut grpc = grpc_client!(self, MintQueryClient);
+
+        let res = timeout(
             self.get_timeout(),
-            MintQueryClient::connect(self.url.clone()),
+            grpc.params(self.query_request(QueryMintParamsRequest {})),
         )
-        .await??;
+        .await??
+        .into_inner();
+
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No mint params returned".to_string(),
+            )),
+        }
+    }
 
-        let res = timeout(self.get_timeout(), grpc.params(QueryMintParamsRequest {}))
-            .await??
-            .into_inner();
+    /// Returns the inflation rate for the chain
+    pub async fn get_inflation(&self) -> Result<Decimal, CosmosGrpcError> {
+        let raw = self.get_raw_inflation().await?;
+        parse_raw_dec(&raw)
+    }
 
-        Ok(res.params.unwrap())
+    /// Returns the inflation rate for the chain as an f64, this may lose precision
+    /// prefer `get_inflation` where possible
+    pub async fn get_inflation_f64(&self) -> Result<f64, CosmosGrpcError> {
+        let raw = self.get_raw_inflation().await?;
+        parse_raw_dec_f64(&raw)
     }
 
-    /// Returns the inflation rate for the chain, in decimal format
-    pub async fn get_inflation(&self) -> Result<f64, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            MintQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+    /// Returns the annual provisions for the chain in terms of the native token per year,
+    /// with the full 18 digits of precision
+    pub async fn get_annual_provisions(&self) -> Result<WideDecimal, CosmosGrpcError> {
+        let raw = self.get_raw_annual_provisions().await?;
+        WideDecimal::from_raw_sdk_dec(&raw).map_err(|e| CosmosGrpcError::BadResponse(e.to_string()))
+    }
 
-        let res = timeout(self.get_timeout(), grpc.inflation(QueryInflationRequest {}))
-            .await??
-            .into_inner();
+    /// Returns the annual provisions for the chain as an f64 in terms of the native token
+    /// per year, this may lose precision
+    pub async fn get_annual_provisions_f64(&self) -> Result<f64, CosmosGrpcError> {
+        let raw = self.get_raw_annual_provisions().await?;
+        parse_raw_dec_f64(&raw)
+    }
 
-        let string = String::from_utf8(res.inflation).unwrap();
-        let float: f64 = string.parse().unwrap();
-        Ok(float / DEC_MANTISSA)
+    /// Estimates the current staking APR of the chain as
+    /// `annual_provisions * (1 - community_tax) / bonded_tokens`
+    /// This assumes the current annual provisions and bonded tokens stay constant for the year
+    /// and ignores validator commission, proposer rewards, and transaction fees, so it is the
+    /// yield before commission. The calculation is done on the raw sdk.Dec integers so chains
+    /// with provisions far beyond the range of Decimal are supported
+    pub async fn estimate_staking_apr(&self) -> Result<Decimal, CosmosGrpcError> {
+        let provisions = self.get_raw_annual_provisions().await?;
+        let pool = self.get_staking_pool_info().await?;
+        let params = self.get_distribution_params().await?;
+        let bonded_tokens: Uint256 = pool
+            .bonded_tokens
+            .parse()
+            .map_err(|e| CosmosGrpcError::ParseError { error: e })?;
+        compute_staking_apr(&provisions, &params.community_tax, bonded_tokens)
     }
 
-    /// Returns the annual provisions for the chain, in decimal format in terms of the native token per year
-    pub async fn get_annual_provisions(&self) -> Result<f64, CosmosGrpcError> {
-        let mut grpc = timeout(
+    /// Queries the inflation rate returning the raw sdk.Dec integer string
+    async fn get_raw_inflation(&self) -> Result<String, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, MintQueryClient);
+
+        let res = timeout(
             self.get_timeout(),
-            MintQueryClient::connect(self.url.clone()),
+            grpc.inflation(self.query_request(QueryInflationRequest {})),
         )
-        .await??;
+        .await??
+        .into_inner();
+
+        String::from_utf8(res.inflation)
+            .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid inflation {e}")))
+    }
+
+    /// Queries the annual provisions returning the raw sdk.Dec integer string
+    async fn get_raw_annual_provisions(&self) -> Result<String, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, MintQueryClient);
 
         let res = timeout(
             self.get_timeout(),
-            grpc.annual_provisions(QueryAnnualProvisionsRequest {}),
+            grpc.annual_provisions(self.query_request(QueryAnnualProvisionsRequest {})),
         )
         .await??
         .into_inner();
-        let string = String::from_utf8(res.annual_provisions).unwrap();
-        let float: f64 = string.parse().unwrap();
-        Ok(float / DEC_MANTISSA)
+
+        String::from_utf8(res.annual_provisions)
+            .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid annual provisions {e}")))
+    }
+}
+
+/// Computes `annual_provisions * (1 - community_tax) / bonded_tokens` where the provisions and
+/// tax are raw sdk.Dec integers, without going through the limited range of Decimal until the end
+fn compute_staking_apr(
+    raw_annual_provisions: &str,
+    raw_community_tax: &str,
+    bonded_tokens: Uint256,
+) -> Result<Decimal, CosmosGrpcError> {
+    let one: Uint256 = 10u128.pow(18).into();
+    let provisions: Uint256 = raw_annual_provisions
+        .parse()
+        .map_err(|e| CosmosGrpcError::ParseError { error: e })?;
+    let community_tax: Uint256 = raw_community_tax
+        .parse()
+        .map_err(|e| CosmosGrpcError::ParseError { error: e })?;
+    if community_tax > one {
+        return Err(CosmosGrpcError::BadResponse(format!(
+            "Invalid community tax {raw_community_tax}"
+        )));
+    }
+    if bonded_tokens == 0u8.into() {
+        return Err(CosmosGrpcError::BadResponse(
+            "No bonded tokens, APR is undefined".to_string(),
+        ));
     }
+    let apr = provisions * (one - community_tax) / (bonded_tokens * one);
+    parse_raw_dec(&apr.to_string())
+}
+
+fn parse_raw_dec(raw: &str) -> Result<Decimal, CosmosGrpcError> {
+    Decimal::from_raw_sdk_dec(raw).map_err(|e| CosmosGrpcError::BadResponse(e.to_string()))
+}
+
+fn parse_raw_dec_f64(raw: &str) -> Result<f64, CosmosGrpcError> {
+    let float: f64 = raw
+        .parse()
+        .map_err(|e| CosmosGrpcError::BadResponse(format!("Invalid sdk.Dec {raw} {e}")))?;
+    Ok(float / DEC_MANTISSA)
 }
 
 #[cfg(test)]
@@ -88,6 +161,30 @@ mod tests {
 
     const TIMEOUT: Duration = Duration::from_secs(5);
 
+    #[test]
+    fn test_compute_staking_apr() {
+        // 1000 tokens per year, 2% community tax, 5000 bonded
+        let apr = compute_staking_apr(
+            "1000000000000000000000",
+            "20000000000000000",
+            5000u32.into(),
+        )
+        .unwrap();
+        assert_eq!(apr.to_string(), "0.196000000000000000");
+
+        // provisions far beyond the range of Decimal
+        let apr = compute_staking_apr(
+            "100000000000000000000000000000000000000000",
+            "0",
+            300_000_000_000_000_000_000_000u128.into(),
+        )
+        .unwrap();
+        assert_eq!(apr.to_string(), "0.333333333333333333");
+
+        assert!(compute_staking_apr("1000", "0", 0u8.into()).is_err());
+        assert!(compute_staking_apr("1000", "2000000000000000000", 5u8.into()).is_err());
+    }
+
     #[tokio::test]
     async fn test_get_inflation() {
         let contact = Contact::new("https://gravitychain.io:9090", TIMEOUT, "gravity").unwrap();
@@ -1,21 +1,63 @@
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Creates a gRPC client of the given generated client type connected to the server of a
+/// Contact, applying the compression and message size settings of the Contact. This should be
+/// used to create every client so that settings apply uniformly, including raw
+/// `tonic::client::Grpc` clients for queries missing from cosmos-sdk-proto. It must be used in
+/// a function returning a Result with CosmosGrpcError as the error type
+macro_rules! grpc_client {
+    ($contact:expr, $client:ident) => {{
+        let mut client = $client::new($contact.get_channel().await?);
+        if $contact.accept_compression {
+            client = client.accept_compressed(tonic::codec::CompressionEncoding::Gzip);
+        }
+        if let Some(limit) = $contact.max_decoding_message_size {
+            client = client.max_decoding_message_size(limit);
+        }
+        client
+    }};
+}
 
 pub mod auth;
+pub mod authz;
 pub mod bank;
 pub mod distribution;
+#[cfg(feature = "althea")]
+pub mod erc20;
+pub mod feegrant;
+#[cfg(feature = "ethermint")]
+pub mod feemarket;
 pub mod get;
 pub mod gov;
+pub mod ibc;
 pub mod invariant;
+#[cfg(feature = "althea")]
+pub mod microtx;
 pub mod mint;
 pub mod send;
+pub mod sequence;
+pub mod slashing;
 pub mod staking;
 pub mod type_urls;
 pub mod types;
+pub mod upgrade;
+pub mod vesting;
 
-use cosmos_sdk_proto::cosmos::base::query::v1beta1::PageRequest;
+pub use crate::error::CosmosGrpcError;
+use cosmos_sdk_proto::cosmos::base::query::v1beta1::{PageRequest, PageResponse};
 pub use types::ChainStatus;
 
-use crate::{error::CosmosGrpcError, utils::ArrayString};
+use crate::address::{get_module_account_address, validate_prefix, Address};
+use crate::error::AddressError;
+use crate::utils::{historical_grpc_query, ArrayString};
+use std::future::Future;
+use std::ops::Deref;
+use tokio::time::{sleep, timeout};
+#[cfg(feature = "ssl")]
+use tonic::transport::ClientTlsConfig;
+use tonic::transport::{Channel, Endpoint};
+use tonic::{IntoRequest, Request};
 
 pub const MEMO: &str = "Sent with Deep Space";
 
@@ -33,18 +75,103 @@ pub const PAGE: Option<PageRequest> = Some(PageRequest {
     reverse: false,
 });
 
+/// Repeatedly calls `fetch` with a pagination request, following `next_key` until the last
+/// page has been returned, and accumulates the items from every page. `fetch` returns the
+/// items of one page along with the pagination response of the query
+pub(crate) async fn collect_pages<T, F, Fut>(mut fetch: F) -> Result<Vec<T>, CosmosGrpcError>
+where
+    F: FnMut(PageRequest) -> Fut,
+    Fut: Future<Output = Result<(Vec<T>, Option<PageResponse>), CosmosGrpcError>>,
+{
+    let mut page = PAGE.unwrap();
+    let mut out = Vec::new();
+    loop {
+        let (items, page_response) = fetch(page.clone()).await?;
+        out.extend(items);
+        match page_response {
+            Some(page_response) if !page_response.next_key.is_empty() => {
+                page.key = page_response.next_key
+            }
+            _ => break,
+        }
+    }
+    Ok(out)
+}
+
 /// An instance of Contact Cosmos RPC Client.
 #[derive(Clone)]
 pub struct Contact {
     /// The GRPC server url, we connect to this address
     /// with a new instance for each call to ensure
-    /// proper failover
+    /// proper failover, unless channel reuse is enabled
     url: String,
     /// The maximum amount of wall time any action taken
     /// will wait for.
     timeout: Duration,
     /// The prefix being used by this node / chain for Addresses
     chain_prefix: String,
+    /// The number of times we will try to establish a connection
+    /// to the server before giving up, defaults to a single attempt
+    connection_attempts: u32,
+    /// If channel reuse is enabled this holds the lazily established
+    /// channel, shared between all clones of this Contact
+    channel: Option<Arc<Mutex<Option<Channel>>>>,
+    /// If set, queries made by this Contact are made against the state
+    /// at this block height rather than the latest state, see `at_height`
+    height: Option<u64>,
+    /// The MaxMemoCharacters auth param of the chain, queried on first use and
+    /// shared between all clones of this Contact
+    max_memo_characters: Arc<Mutex<Option<u64>>>,
+    /// The chain-id of the chain, fetched on first use and updated whenever a block header
+    /// is read, shared between all clones of this Contact
+    chain_id: Arc<Mutex<Option<String>>>,
+    /// The chain-id pinned with `set_chain_id`, used instead of the chain-id reported by
+    /// the chain and shared between all clones of this Contact
+    pinned_chain_id: Arc<Mutex<Option<String>>>,
+    /// If true gRPC clients advertise that they accept gzip compressed responses,
+    /// off by default since some load balancers do not handle compression correctly
+    accept_compression: bool,
+    /// If true send_coins checks the bank params and refuses to send denoms that
+    /// have sending disabled, rather than failing on chain
+    check_send_enabled: bool,
+    /// The maximum size in bytes of a response the gRPC clients will decode, if None
+    /// tonic's default limit of 4MB is used
+    max_decoding_message_size: Option<usize>,
+    /// Custom TLS settings for https endpoints, such as a private CA or a client
+    /// certificate, the system roots are used if this is None
+    #[cfg(feature = "ssl")]
+    tls_config: Option<ClientTlsConfig>,
+}
+
+/// A Contact that makes module state queries against the chain state at a fixed historical
+/// height, created with `Contact::at_height`, e.g. `contact.at_height(1000).get_balances(address)`.
+/// The height applies to every query of the auth, authz, bank, distribution, feegrant, gov, ibc,
+/// mint, slashing, staking and upgrade modules, as well as the erc20, feemarket and microtx
+/// modules when their features are enabled. The block, transaction and node queries, such as
+/// `get_latest_block`, `get_chain_status` and `get_tx_by_hash`, are not state queries and always
+/// use the latest state. The node must not have pruned the requested height.
+///
+/// The transaction sending methods of Contact are also reachable through this type but should
+/// not be used, since account info queried at a past height will have an outdated sequence,
+/// send transactions with a regular Contact instead
+#[derive(Clone)]
+pub struct HeightScopedContact {
+    contact: Contact,
+}
+
+impl HeightScopedContact {
+    pub fn get_height(&self) -> u64 {
+        // always set when constructed through at_height
+        self.contact.height.unwrap()
+    }
+}
+
+impl Deref for HeightScopedContact {
+    type Target = Contact;
+
+    fn deref(&self) -> &Contact {
+        &self.contact
+    }
 }
 
 impl Contact {
@@ -54,17 +181,105 @@ impl Contact {
             url = url.trim_end_matches('/');
         }
         ArrayString::new(chain_prefix)?;
+        if validate_prefix(chain_prefix).is_err() {
+            return Err(CosmosGrpcError::InvalidPrefix);
+        }
         Ok(Self {
             url: url.to_string(),
             timeout,
             chain_prefix: chain_prefix.to_string(),
+            connection_attempts: 1,
+            channel: None,
+            height: None,
+            max_memo_characters: Arc::new(Mutex::new(None)),
+            chain_id: Arc::new(Mutex::new(None)),
+            pinned_chain_id: Arc::new(Mutex::new(None)),
+            accept_compression: false,
+            check_send_enabled: false,
+            max_decoding_message_size: None,
+            #[cfg(feature = "ssl")]
+            tls_config: None,
         })
     }
 
+    /// The same as `new` but also probes the node by querying its sync status, so that an
+    /// unreachable or misconfigured endpoint is reported when the Contact is created rather
+    /// than on first use. Returns ConnectionError if the node can not be reached
+    pub async fn new_checked(
+        url: &str,
+        timeout: Duration,
+        chain_prefix: &str,
+    ) -> Result<Self, CosmosGrpcError> {
+        let contact = Contact::new(url, timeout, chain_prefix)?;
+        contact.get_chain_status().await?;
+        Ok(contact)
+    }
+
+    /// Creates a Contact that connects using the provided TLS settings rather than the system
+    /// roots, for nodes using self signed or internal CA certificates or requiring a client
+    /// certificate. `url` should be an https endpoint
+    #[cfg(feature = "ssl")]
+    pub fn new_with_tls(
+        url: &str,
+        timeout: Duration,
+        chain_prefix: &str,
+        tls_config: ClientTlsConfig,
+    ) -> Result<Self, CosmosGrpcError> {
+        let mut contact = Contact::new(url, timeout, chain_prefix)?;
+        contact.tls_config = Some(tls_config);
+        Ok(contact)
+    }
+
+    /// Creates a Contact that will decode responses of up to `bytes` in size, rather than
+    /// tonic's default limit of 4MB. See `set_max_decoding_message_size`
+    pub fn new_with_max_message_size(
+        url: &str,
+        timeout: Duration,
+        chain_prefix: &str,
+        bytes: usize,
+    ) -> Result<Self, CosmosGrpcError> {
+        let mut contact = Contact::new(url, timeout, chain_prefix)?;
+        contact.max_decoding_message_size = Some(bytes);
+        Ok(contact)
+    }
+
+    /// Creates a Contact that accepts gzip compressed responses from the server, rather than
+    /// the uncompressed default of `new`. See `set_accept_compression`
+    pub fn new_with_compression(
+        url: &str,
+        timeout: Duration,
+        chain_prefix: &str,
+    ) -> Result<Self, CosmosGrpcError> {
+        let mut contact = Contact::new(url, timeout, chain_prefix)?;
+        contact.accept_compression = true;
+        Ok(contact)
+    }
+
+    /// Creates a Contact that establishes a single gRPC channel on first use and reuses it
+    /// for every following call, rather than opening a new connection per call like `new`.
+    /// This greatly improves throughput when making many queries, for example in a loop,
+    /// but trades away failover, if the server behind `url` changes (such as a load balancer
+    /// rotating nodes) the existing channel will keep talking to the original node
+    pub fn new_with_channel_reuse(
+        url: &str,
+        timeout: Duration,
+        chain_prefix: &str,
+    ) -> Result<Self, CosmosGrpcError> {
+        let mut contact = Contact::new(url, timeout, chain_prefix)?;
+        contact.channel = Some(Arc::new(Mutex::new(None)));
+        Ok(contact)
+    }
+
     pub fn get_prefix(&self) -> String {
         self.chain_prefix.clone()
     }
 
+    /// Computes the address of the named module account, such as "gov" or "distribution",
+    /// using the prefix of this Contact. See `get_module_account_address` for details
+    pub fn module_account_address(&self, module_name: &str) -> Result<Address, AddressError> {
+        get_module_account_address(module_name, Some(&self.chain_prefix))
+    }
+
     pub fn get_url(&self) -> String {
         self.url.clone()
     }
@@ -72,6 +287,149 @@ impl Contact {
     pub fn get_timeout(&self) -> Duration {
         self.timeout
     }
+
+    pub fn get_connection_attempts(&self) -> u32 {
+        self.connection_attempts
+    }
+
+    /// Sets the number of times a connection to the server will be attempted
+    /// before returning an error, useful for unreliable endpoints. Failed attempts
+    /// are retried with exponential backoff, bounded by the timeout of this Contact.
+    /// A value of zero is treated as one.
+    pub fn set_connection_attempts(&mut self, attempts: u32) {
+        self.connection_attempts = attempts.max(1);
+    }
+
+    pub fn get_accept_compression(&self) -> bool {
+        self.accept_compression
+    }
+
+    /// Sets if gzip compressed responses are accepted from the server, this applies
+    /// to every query made by this Contact and can greatly reduce the size of large
+    /// responses, but some proxies and load balancers break compressed gRPC responses
+    pub fn set_accept_compression(&mut self, accept_compression: bool) {
+        self.accept_compression = accept_compression;
+    }
+
+    pub fn get_check_send_enabled(&self) -> bool {
+        self.check_send_enabled
+    }
+
+    /// Sets if send_coins should query the bank params before sending and return an
+    /// error for any denom with sending disabled, this costs an extra query per send
+    pub fn set_check_send_enabled(&mut self, check_send_enabled: bool) {
+        self.check_send_enabled = check_send_enabled;
+    }
+
+    pub fn get_max_decoding_message_size(&self) -> Option<usize> {
+        self.max_decoding_message_size
+    }
+
+    /// Sets the maximum size in bytes of a response that will be decoded, this applies to
+    /// every query made by this Contact. Responses over the limit, such as large blocks from
+    /// `get_block` or `get_block_range` on busy chains, fail with a RequestError reporting
+    /// "message length too large", raising this limit resolves those errors. None restores
+    /// tonic's default limit of 4MB
+    pub fn set_max_decoding_message_size(&mut self, bytes: Option<usize>) {
+        self.max_decoding_message_size = bytes;
+    }
+
+    /// Pins the chain-id used when signing transactions, rather than using the chain-id
+    /// of the latest block. This applies to all clones of this Contact
+    pub fn set_chain_id(&self, chain_id: impl Into<String>) {
+        *self.pinned_chain_id.lock().unwrap() = Some(chain_id.into());
+    }
+
+    /// Returns the chain-id to sign with given the chain-id of a freshly read block header,
+    /// the pinned chain-id if any and otherwise the header's, which also updates the cache so
+    /// that a chain-id change after an upgrade is picked up
+    pub(crate) fn resolve_chain_id(&self, header_chain_id: String) -> String {
+        if let Some(pinned) = self.pinned_chain_id.lock().unwrap().clone() {
+            return pinned;
+        }
+        *self.chain_id.lock().unwrap() = Some(header_chain_id.clone());
+        header_chain_id
+    }
+
+    /// Returns a view of this Contact that makes module state queries against the chain state
+    /// at the given block `height`, see HeightScopedContact for exactly which queries this
+    /// applies to. The returned value shares the connection settings (and reused channel if
+    /// any) of this Contact
+    pub fn at_height(&self, height: u64) -> HeightScopedContact {
+        let mut contact = self.clone();
+        contact.height = Some(height);
+        HeightScopedContact { contact }
+    }
+
+    /// Builds a gRPC request for a query, attaching the historical height header
+    /// if this Contact is scoped to a height. All query requests should be made through
+    /// this so that `at_height` applies uniformly
+    pub(crate) fn query_request<T>(&self, req: impl IntoRequest<T>) -> Request<T> {
+        match self.height {
+            Some(height) => historical_grpc_query(req, height),
+            None => req.into_request(),
+        }
+    }
+
+    /// Returns a connected gRPC channel to the server of this Contact, following the same
+    /// connection retry and channel reuse settings as the rest of the crate. This is an escape
+    /// hatch for querying modules this crate does not wrap, any generated tonic client can be
+    /// built from the channel. Calls made this way bypass the crate's handling of timeouts
+    /// and error normalization, so callers should apply `get_timeout` themselves
+    pub async fn raw_channel(&self) -> Result<Channel, CosmosGrpcError> {
+        self.get_channel().await
+    }
+
+    /// Returns a gRPC channel to the server, this is the shared channel if channel reuse
+    /// is enabled, establishing it if needed, otherwise a fresh connection
+    pub(crate) async fn get_channel(&self) -> Result<Channel, CosmosGrpcError> {
+        match &self.channel {
+            Some(shared) => {
+                let existing = shared.lock().unwrap().clone();
+                if let Some(channel) = existing {
+                    return Ok(channel);
+                }
+                let channel = self.connect_with_retry().await?;
+                *shared.lock().unwrap() = Some(channel.clone());
+                Ok(channel)
+            }
+            None => self.connect_with_retry().await,
+        }
+    }
+
+    /// Opens a new gRPC channel to the server, retrying failed connection attempts with
+    /// exponential backoff until `connection_attempts` is reached or the timeout runs out
+    pub(crate) async fn connect_with_retry(&self) -> Result<Channel, CosmosGrpcError> {
+        let start = Instant::now();
+        let mut backoff = Duration::from_millis(100);
+        let mut attempt = 1;
+        loop {
+            let remaining = self.timeout.saturating_sub(start.elapsed());
+            #[allow(unused_mut)]
+            let mut endpoint = Endpoint::new(self.url.clone())?;
+            #[cfg(feature = "ssl")]
+            if let Some(tls_config) = &self.tls_config {
+                endpoint = endpoint.tls_config(tls_config.clone())?;
+            }
+            match timeout(remaining, endpoint.connect()).await? {
+                Ok(channel) => return Ok(channel),
+                Err(e) => {
+                    if attempt >= self.connection_attempts
+                        || start.elapsed() + backoff >= self.timeout
+                    {
+                        return Err(CosmosGrpcError::ConnectionError { error: e });
+                    }
+                    warn!(
+                        "Failed to connect to {} on attempt {} with {:?}, retrying",
+                        self.url, attempt, e
+                    );
+                    sleep(backoff).await;
+                    backoff *= 2;
+                    attempt += 1;
+                }
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -101,4 +459,165 @@ mod tests {
 
         let _ = contact.get_all_accounts().await.unwrap();
     }
+
+    #[actix_rt::test]
+    async fn test_connect_with_retry_gives_up() {
+        let mut contact =
+            Contact::new("http://127.0.0.1:1", Duration::from_secs(5), "cosmos").unwrap();
+        contact.set_connection_attempts(3);
+        let start = Instant::now();
+        let res = contact.connect_with_retry().await;
+        assert!(matches!(res, Err(CosmosGrpcError::ConnectionError { .. })));
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+    #[test]
+    fn test_module_account_address() {
+        let contact = Contact::new("http://127.0.0.1:9090", TIMEOUT, "gravity").unwrap();
+        let gov = contact.module_account_address("gov").unwrap();
+        assert_eq!(gov.get_prefix(), "gravity");
+        assert_eq!(
+            gov,
+            get_module_account_address("gov", Some("gravity")).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_at_height() {
+        let contact = Contact::new("http://127.0.0.1:9090", TIMEOUT, "cosmos").unwrap();
+        let request = contact.query_request(());
+        assert!(request.metadata().get("x-cosmos-block-height").is_none());
+
+        let historical = contact.at_height(1000);
+        assert_eq!(historical.get_height(), 1000);
+        assert_eq!(historical.get_prefix(), "cosmos");
+        let request = historical.query_request(());
+        assert_eq!(
+            request.metadata().get("x-cosmos-block-height").unwrap(),
+            "1000"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_chain_id_cache() {
+        // nothing listens here, so this only passes if no request is made
+        let contact = Contact::new("http://127.0.0.1:1", TIMEOUT, "cosmos").unwrap();
+        let clone = contact.clone();
+        contact.set_chain_id("test-chain-1");
+        assert_eq!(clone.get_chain_id().await.unwrap(), "test-chain-1");
+    }
+
+    #[actix_rt::test]
+    async fn test_resolve_chain_id() {
+        let contact = Contact::new("http://127.0.0.1:1", TIMEOUT, "cosmos").unwrap();
+        // the header chain-id replaces the cached one, for example after an upgrade
+        assert_eq!(
+            contact.resolve_chain_id("test-chain-1".to_string()),
+            "test-chain-1"
+        );
+        assert_eq!(
+            contact.resolve_chain_id("test-chain-2".to_string()),
+            "test-chain-2"
+        );
+        assert_eq!(contact.get_chain_id().await.unwrap(), "test-chain-2");
+        // unless one was pinned
+        contact.set_chain_id("pinned-1");
+        assert_eq!(
+            contact.resolve_chain_id("test-chain-3".to_string()),
+            "pinned-1"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_collect_pages() {
+        let pages = vec![vec![1, 2], vec![3], vec![4, 5]];
+        let mut requested_keys = Vec::new();
+        let result = collect_pages(|page| {
+            requested_keys.push(page.key.clone());
+            let index = page.key.first().copied().unwrap_or(0) as usize;
+            let items = pages[index].clone();
+            let next_key = if index + 1 < pages.len() {
+                vec![index as u8 + 1]
+            } else {
+                Vec::new()
+            };
+            async move { Ok((items, Some(PageResponse { next_key, total: 0 }))) }
+        })
+        .await
+        .unwrap();
+        assert_eq!(result, vec![1, 2, 3, 4, 5]);
+        assert_eq!(requested_keys, vec![vec![], vec![1], vec![2]]);
+
+        // no pagination response means there is only one page
+        let result = collect_pages(|_| async { Ok((vec![1], None)) })
+            .await
+            .unwrap();
+        assert_eq!(result, vec![1]);
+    }
+
+    #[actix_rt::test]
+    async fn test_new_checked_unreachable() {
+        // nothing listens on port 1 so connecting fails immediately
+        let res =
+            Contact::new_checked("http://127.0.0.1:1", Duration::from_secs(5), "cosmos").await;
+        assert!(matches!(res, Err(CosmosGrpcError::ConnectionError { .. })));
+        assert!(
+            Contact::new_checked("http://127.0.0.1:1", TIMEOUT, "Cosmos")
+                .await
+                .is_err()
+        );
+    }
+
+    #[cfg(feature = "ssl")]
+    #[test]
+    fn test_new_with_tls() {
+        let contact = Contact::new_with_tls(
+            "https://localhost:9090",
+            TIMEOUT,
+            "cosmos",
+            ClientTlsConfig::new().domain_name("localhost"),
+        )
+        .unwrap();
+        assert!(contact.tls_config.is_some());
+        assert!(Contact::new("https://localhost:9090", TIMEOUT, "cosmos")
+            .unwrap()
+            .tls_config
+            .is_none());
+    }
+
+    #[test]
+    fn test_max_decoding_message_size() {
+        let mut contact =
+            Contact::new_with_max_message_size("http://localhost:9090", TIMEOUT, "cosmos", 1 << 26)
+                .unwrap();
+        assert_eq!(contact.get_max_decoding_message_size(), Some(1 << 26));
+        contact.set_max_decoding_message_size(None);
+        assert_eq!(contact.get_max_decoding_message_size(), None);
+    }
+
+    #[test]
+    fn test_accept_compression() {
+        let mut contact =
+            Contact::new_with_compression("http://localhost:9090", TIMEOUT, "cosmos").unwrap();
+        assert!(contact.get_accept_compression());
+        contact.set_accept_compression(false);
+        assert!(!contact.get_accept_compression());
+        assert!(!Contact::new("http://localhost:9090", TIMEOUT, "cosmos")
+            .unwrap()
+            .get_accept_compression());
+    }
+
+    #[test]
+    fn test_error_reexport() {
+        // fails to compile if the two paths are ever different types
+        let error: crate::error::CosmosGrpcError = crate::client::CosmosGrpcError::NoToken;
+        assert!(matches!(error, crate::client::CosmosGrpcError::NoToken));
+    }
+
+    #[test]
+    fn test_invalid_prefix() {
+        for prefix in ["Cosmos", "cos1mos", ""] {
+            let res = Contact::new("http://127.0.0.1:9090", TIMEOUT, prefix);
+            assert!(matches!(res, Err(CosmosGrpcError::InvalidPrefix)));
+        }
+    }
 }
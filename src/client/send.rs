@@ -1,19 +1,30 @@
 use crate::address::Address;
+use crate::amino::AminoMsg;
 #[cfg(feature = "althea")]
 use crate::client::type_urls::MSG_MICROTX_TYPE_URL;
+use crate::client::type_urls::MSG_MULTI_SEND_TYPE_URL;
 use crate::client::type_urls::MSG_SEND_TYPE_URL;
+use crate::client::ChainStatus;
 use crate::client::Contact;
 use crate::client::MEMO;
 use crate::coin::Coin;
+use crate::coin::Coins;
 use crate::coin::Fee;
-use crate::error::CosmosGrpcError;
+use crate::coin::Tip;
+use crate::decimal::Decimal;
+use crate::error::{CosmosGrpcError, SdkErrorCode};
 use crate::msg::Msg;
-use crate::private_key::PrivateKey;
+use crate::private_key::{sign_tipped, PrivateKey};
 use crate::utils::check_for_sdk_error;
+use crate::utils::determine_min_fees_and_gas;
+use crate::utils::find_event_attribute;
+use crate::utils::tx_hash;
+use crate::utils::FeeInfo;
+use crate::utils::TxResponseExt;
 use crate::MessageArgs;
 #[cfg(feature = "althea")]
 use althea_proto::althea::microtx::v1::MsgMicrotx;
-use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+use cosmos_sdk_proto::cosmos::bank::v1beta1::{Input, MsgMultiSend, MsgSend, Output};
 use cosmos_sdk_proto::cosmos::base::abci::v1beta1::AbciMessageLog;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastTxRequest;
@@ -23,6 +34,10 @@ use cosmos_sdk_proto::cosmos::{
     base::abci::v1beta1::TxResponse, tx::v1beta1::service_client::ServiceClient as TxServiceClient,
 };
 use cosmos_sdk_proto::tendermint::abci::Event;
+use cosmos_sdk_proto::tendermint::types::Block;
+use num256::Uint256;
+use num_traits::CheckedMul;
+use prost::Message;
 use std::fmt;
 use std::fmt::Debug;
 use std::time::Instant;
@@ -31,6 +46,26 @@ use tokio::time::sleep;
 use tokio::time::timeout;
 use tonic::Code as TonicCode;
 
+/// The default multiplier applied to simulated gas usage by `get_fee_info`
+pub const DEFAULT_GAS_ADJUSTMENT: f64 = 2.0;
+
+/// Computes `gas_limit * gas_price` rounded up to a whole amount, done on the raw
+/// 18 digit representation of the price so that large prices can't overflow
+fn fee_for_gas(gas_limit: u64, gas_price: Decimal) -> Result<Uint256, CosmosGrpcError> {
+    let raw_price: Uint256 = gas_price.to_raw_sdk_dec().parse().map_err(|_| {
+        CosmosGrpcError::BadInput(format!("Gas price {gas_price} must not be negative"))
+    })?;
+    let precision: Uint256 = 10u64.pow(18).into();
+    let product = raw_price
+        .checked_mul(&Uint256::from(gas_limit))
+        .ok_or_else(|| CosmosGrpcError::BadInput(format!("Gas price {gas_price} is too large")))?;
+    let mut amount = product / precision;
+    if amount * precision != product {
+        amount += 1u8.into();
+    }
+    Ok(amount)
+}
+
 impl Contact {
     /// Sends an already serialized and signed transaction, checking for various errors in the
     /// transaction response. This is the lowest level transaction sending function and you
@@ -49,7 +84,7 @@ impl Contact {
     /// use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
     /// use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
     /// use deep_space::{Coin, client::Contact, Fee, MessageArgs, Msg, CosmosPrivateKey, PrivateKey, PublicKey};
-    /// use deep_space::client::type_urls::SECP256K1_PUBKEY_TYPE_URL;
+    /// use deep_space::client::type_urls::MSG_SEND_TYPE_URL;
     /// use std::time::Duration;
     /// let private_key = CosmosPrivateKey::from_secret("mySecret".as_bytes());
     /// let public_key = private_key.to_public_key("cosmospub").unwrap();
@@ -69,7 +104,7 @@ impl Contact {
     ///     granter: None,
     ///     payer: None,
     /// };
-    /// let msg = Msg::new(SECP256K1_PUBKEY_TYPE_URL, send);
+    /// let msg = Msg::new(MSG_SEND_TYPE_URL, send);
     /// let args = MessageArgs {
     ///     sequence: 0,
     ///     account_number: 0,
@@ -89,8 +124,7 @@ impl Contact {
         msg: Vec<u8>,
         mode: BroadcastMode,
     ) -> Result<TransactionResponse, CosmosGrpcError> {
-        let mut txrpc =
-            timeout(self.get_timeout(), TxServiceClient::connect(self.get_url())).await??;
+        let mut txrpc = grpc_client!(self, TxServiceClient);
         let response = timeout(
             self.get_timeout(),
             txrpc.broadcast_tx(BroadcastTxRequest {
@@ -126,7 +160,7 @@ impl Contact {
     /// use cosmos_sdk_proto::cosmos::tx::v1beta1::BroadcastMode;
     /// use deep_space::{Coin, client::Contact, Fee, MessageArgs, Msg, CosmosPrivateKey, PrivateKey, PublicKey};
     /// use std::time::Duration;
-    /// use deep_space::client::type_urls::SECP256K1_PUBKEY_TYPE_URL;
+    /// use deep_space::client::type_urls::MSG_SEND_TYPE_URL;
     /// let private_key = CosmosPrivateKey::from_secret("mySecret".as_bytes());
     /// let public_key = private_key.to_public_key("cosmospub").unwrap();
     /// let address = public_key.to_address();
@@ -139,11 +173,15 @@ impl Contact {
     ///     from_address: address.to_string(),
     ///     to_address: "cosmos1pr2n6tfymnn2tk6rkxlu9q5q2zq5ka3wtu7sdj".to_string(),
     /// };
-    /// let msg = Msg::new(SECP256K1_PUBKEY_TYPE_URL, send);
+    /// let msg = Msg::new(MSG_SEND_TYPE_URL, send);
     /// let contact = Contact::new("https:://your-grpc-server", Duration::from_secs(5), "prefix").unwrap();
     /// // future must be awaited in tokio runtime
     /// contact.send_message(&vec![msg], None, &[coin], None, None, private_key);
     /// ```
+    ///
+    /// If the transaction is rejected because the account sequence changed after it was
+    /// queried (for example when another transaction from this key was sent at the same time)
+    /// the account info is re-fetched and the transaction is sent once more.
     pub async fn send_message(
         &self,
         messages: &[Msg],
@@ -152,6 +190,69 @@ impl Contact {
         wait_timeout: Option<Duration>,
         block_timeout: Option<u64>,
         private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let res = self
+            .send_message_no_retry(
+                messages,
+                memo.clone(),
+                fee_coin,
+                wait_timeout,
+                block_timeout,
+                private_key.clone(),
+            )
+            .await;
+        match res {
+            Err(e) if is_sequence_error(&e) => {
+                warn!("Transaction failed with sequence error {:?}, retrying", e);
+                self.send_message_no_retry(
+                    messages,
+                    memo,
+                    fee_coin,
+                    wait_timeout,
+                    block_timeout,
+                    private_key,
+                )
+                .await
+            }
+            res => res,
+        }
+    }
+
+    /// The same as `send_message` but without retrying on an account sequence error,
+    /// useful when the caller manages sequences themselves
+    pub async fn send_message_no_retry(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee_coin: &[Coin],
+        wait_timeout: Option<Duration>,
+        block_timeout: Option<u64>,
+        private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        self.send_message_with_mode(
+            messages,
+            memo,
+            fee_coin,
+            wait_timeout,
+            block_timeout,
+            BroadcastMode::Sync,
+            private_key,
+        )
+        .await
+    }
+
+    /// The same as `send_message_no_retry` but broadcasting with the provided `mode`
+    /// rather than `BroadcastMode::Sync`. See `send_message_with_args_and_mode` for details
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_message_with_mode(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee_coin: &[Coin],
+        wait_timeout: Option<Duration>,
+        block_timeout: Option<u64>,
+        mode: BroadcastMode,
+        private_key: impl PrivateKey,
     ) -> Result<TransactionResponse, CosmosGrpcError> {
         let our_address = private_key.to_address(&self.chain_prefix).unwrap();
 
@@ -163,10 +264,149 @@ impl Contact {
             .await?;
         trace!("got optional tx info");
 
+        self.send_message_with_args_and_mode(messages, memo, args, wait_timeout, mode, private_key)
+            .await
+    }
+
+    /// Sends an arbitrary vector of messages using a fully specified fee, including the
+    /// fee granter and payer. Unlike `send_message` no simulation is performed and the
+    /// provided gas limit is used as is, this is required when paying fees through
+    /// the feegrant module or when a third party is paying the fee for this transaction.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - An array of messages to send
+    /// * `memo` - An optional memo to be included in the transaction, if None the default memo value is set
+    /// * `fee` - The complete fee to use for this transaction, including the gas limit
+    /// * `wait_timeout` - An optional amount of time to wait for the transaction to enter the blockchain
+    /// * `block_timeout` - An optional number of blocks into the future that this transaction should be valid for.
+    ///   If None, DEFAULT_TRANSACTION_TIMEOUT_BLOCKS is used.
+    /// * `private_key` - A private key used to sign and send the transaction
+    pub async fn send_message_with_fee(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        fee: Fee,
+        wait_timeout: Option<Duration>,
+        block_timeout: Option<u64>,
+        private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+
+        let args = self
+            .get_message_args(our_address, fee, block_timeout)
+            .await?;
+        trace!("got optional tx info");
+
         self.send_message_with_args(messages, memo, args, wait_timeout, private_key)
             .await
     }
 
+    /// Sends a tipped transaction, `tipper_key` signs the messages and pays `tip` while
+    /// `fee_payer_key` pays `fee`. This allows the tipper to pay for its transaction in a
+    /// token other than the fee token on chains that support tips, with the fee payer being
+    /// compensated by the tip. No simulation is performed so `fee` must include a sufficient
+    /// gas limit, the fee payer of `fee` and tipper of `tip` are filled in from the keys
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - An array of messages to send, signed by the tipper
+    /// * `memo` - An optional memo to be included in the transaction, if None the default memo value is set
+    /// * `tip` - The tip paid by the tipper to the fee payer
+    /// * `fee` - The complete fee paid by the fee payer, including the gas limit
+    /// * `tipper_key` - The private key of the tipper
+    /// * `fee_payer_key` - The private key of the fee payer
+    /// * `wait_timeout` - An optional amount of time to wait for the transaction to enter the blockchain
+    #[allow(clippy::too_many_arguments)]
+    pub async fn send_message_with_tip(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        mut tip: Tip,
+        mut fee: Fee,
+        tipper_key: impl PrivateKey,
+        fee_payer_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let tipper_address = tipper_key.to_address(&self.chain_prefix).unwrap();
+        let fee_payer_address = fee_payer_key.to_address(&self.chain_prefix).unwrap();
+        tip.tipper.get_or_insert(tipper_address);
+        fee.payer.get_or_insert(fee_payer_address);
+
+        let tipper_args = self
+            .get_message_args(tipper_address, Fee::default(), None)
+            .await?;
+        let fee_payer_args = self.get_message_args(fee_payer_address, fee, None).await?;
+
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        self.check_memo(&memo).await?;
+        let tx = sign_tipped(
+            messages,
+            &memo,
+            tip,
+            &tipper_key,
+            &tipper_args,
+            &fee_payer_key,
+            &fee_payer_args,
+        )?;
+
+        let response = self
+            .send_transaction(tx.encode_to_vec(), BroadcastMode::Sync)
+            .await?;
+        match wait_timeout {
+            Some(time) => self.wait_for_tx(response, time).await,
+            None => Ok(response),
+        }
+    }
+
+    /// Sends a transaction signed with SIGN_MODE_LEGACY_AMINO_JSON, see
+    /// `PrivateKey::sign_std_msg_amino`. `amino_messages` must hold the amino JSON form of
+    /// each of `messages` in the same order. No simulation is performed so `fee` must
+    /// include a sufficient gas limit
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - An array of messages to send
+    /// * `amino_messages` - The amino JSON form of each message, as signed
+    /// * `memo` - An optional memo to be included in the transaction, if None the default memo value is set
+    /// * `fee` - The complete fee for the transaction, including the gas limit
+    /// * `wait_timeout` - An optional amount of time to wait for the transaction to enter the blockchain
+    /// * `private_key` - A private key used to sign and send the transaction
+    pub async fn send_amino_message(
+        &self,
+        messages: &[Msg],
+        amino_messages: &[AminoMsg],
+        memo: Option<String>,
+        fee: Fee,
+        wait_timeout: Option<Duration>,
+        private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let args = self.get_message_args(our_address, fee, None).await?;
+        let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        self.check_memo(&memo).await?;
+        let tx_bytes = private_key.sign_std_msg_amino(messages, amino_messages, args, &memo)?;
+
+        let response = self.send_transaction(tx_bytes, BroadcastMode::Sync).await?;
+        match wait_timeout {
+            Some(time) => self.wait_for_tx(response, time).await,
+            None => Ok(response),
+        }
+    }
+
+    /// Checks the memo against the MaxMemoCharacters auth param of the chain, this should be
+    /// done by every send path so that an over long memo produces the same local error
+    async fn check_memo(&self, memo: &str) -> Result<(), CosmosGrpcError> {
+        // the node enforces this limit as well, so if the param can't be queried we still send
+        match self.get_max_memo_characters().await {
+            Ok(max) => check_memo_length(memo, max),
+            Err(e) => {
+                warn!("Failed to get max memo length, not checking memo {:?}", e);
+                Ok(())
+            }
+        }
+    }
+
     /// Performs Tx generation, signing, and submission for send_message()
     /// See send_message() for more information
     ///
@@ -178,30 +418,88 @@ impl Contact {
         args: MessageArgs,
         wait_timeout: Option<Duration>,
         private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        self.send_message_with_args_and_mode(
+            messages,
+            memo,
+            args,
+            wait_timeout,
+            BroadcastMode::Sync,
+            private_key,
+        )
+        .await
+    }
+
+    /// The same as `send_message_with_args` but broadcasting with the provided `mode`.
+    /// `BroadcastMode::Async` returns without waiting for any validation of the transaction
+    /// so `wait_timeout` is ignored in that case, `BroadcastMode::Block` is deprecated by
+    /// the Cosmos SDK and may not be supported by the node.
+    pub async fn send_message_with_args_and_mode(
+        &self,
+        messages: &[Msg],
+        memo: Option<String>,
+        args: MessageArgs,
+        wait_timeout: Option<Duration>,
+        mode: BroadcastMode,
+        private_key: impl PrivateKey,
     ) -> Result<TransactionResponse, CosmosGrpcError> {
         let memo = memo.unwrap_or_else(|| MEMO.to_string());
+        self.check_memo(&memo).await?;
         let msg_bytes = private_key.sign_std_msg(messages, args, &memo)?;
 
-        let response = self
-            .send_transaction(msg_bytes, BroadcastMode::Sync)
-            .await?;
+        let response = self.send_transaction(msg_bytes, mode).await?;
 
         trace!("broadcasted! with response {:?}", response);
-        if let Some(time) = wait_timeout {
-            self.wait_for_tx(response, time).await
-        } else {
-            Ok(response)
+        match wait_timeout {
+            Some(_) if mode == BroadcastMode::Async => {
+                warn!("wait_timeout is ignored for BroadcastMode::Async transactions");
+                Ok(response)
+            }
+            Some(time) => self.wait_for_tx(response, time).await,
+            None => Ok(response),
         }
     }
 
     /// Simulates the provided array of messages and returns
-    /// a fee object with the gas amount actually used
+    /// a fee object with the gas amount actually used, multiplied
+    /// by the default gas adjustment of 2.0
     pub async fn get_fee_info(
         &self,
         messages: &[Msg],
         fee_token: &[Coin],
         private_key: impl PrivateKey,
     ) -> Result<Fee, CosmosGrpcError> {
+        // due to this known issue, gas estimation is
+        // inaccurate, normally short about ~20% in my tests
+        // https://github.com/cosmos/cosmos-sdk/issues/4938
+        self.get_fee_info_with_adjustment(messages, fee_token, DEFAULT_GAS_ADJUSTMENT, private_key)
+            .await
+    }
+
+    /// Simulates the provided array of messages and returns a fee object with
+    /// the gas amount actually used multiplied by the provided gas adjustment.
+    /// Chains vary in how far off simulation is from actual usage, so callers
+    /// can tune this to avoid overpaying or running out of gas.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - An array of messages to simulate
+    /// * `fee_token` - A fee amount and coin type to use
+    /// * `multiplier` - The gas adjustment to apply to the simulated gas, must be at least 1.0
+    /// * `private_key` - A private key used to sign the simulated transaction
+    pub async fn get_fee_info_with_adjustment(
+        &self,
+        messages: &[Msg],
+        fee_token: &[Coin],
+        multiplier: f64,
+        private_key: impl PrivateKey,
+    ) -> Result<Fee, CosmosGrpcError> {
+        if multiplier.is_nan() || multiplier < 1.0 {
+            return Err(CosmosGrpcError::BadInput(format!(
+                "Gas adjustment {multiplier} is less than 1.0"
+            )));
+        }
+
         let gas_info = self
             .simulate_tx(messages, Some(fee_token), private_key.clone())
             .await?
@@ -209,13 +507,14 @@ impl Contact {
             .unwrap();
         let gas_used = gas_info.gas_used;
         trace!("Got {} gas used!", gas_used);
+        let gas_limit = (gas_used as f64 * multiplier).ceil() as u64;
 
         let block_params = self.get_block_params().await?;
         if let Some(max_gas) = block_params.max_gas {
-            if gas_used > max_gas {
+            if gas_limit > max_gas {
                 return Err(CosmosGrpcError::GasRequiredExceedsBlockMaximum {
                     max: max_gas,
-                    required: gas_used,
+                    required: gas_limit,
                 });
             }
 
@@ -239,10 +538,32 @@ impl Contact {
             amount: fee_token.to_vec(),
             granter: None,
             payer: None,
-            // due to this known issue, gas estimation is
-            // inaccurate, normally short about ~20% in my tests
-            // https://github.com/cosmos/cosmos-sdk/issues/4938
-            gas_limit: gas_used * 2,
+            gas_limit,
+        })
+    }
+
+    /// Simulates the provided array of messages and returns a fee quote in `denom`, the
+    /// simulated gas with the default gas adjustment applied multiplied by `gas_price`,
+    /// rounded up to the nearest whole unit.
+    ///
+    /// # Arguments
+    ///
+    /// * `messages` - An array of messages to simulate
+    /// * `gas_price` - The amount of `denom` paid per unit of gas, e.g. 0.025
+    /// * `denom` - The denom the fee will be paid in
+    /// * `private_key` - A private key used to sign the simulated transaction
+    pub async fn estimate_fee(
+        &self,
+        messages: &[Msg],
+        gas_price: Decimal,
+        denom: &str,
+        private_key: impl PrivateKey,
+    ) -> Result<Coin, CosmosGrpcError> {
+        let fee = self.get_fee_info(messages, &[], private_key).await?;
+        let amount = fee_for_gas(fee.gas_limit, gas_price)?;
+        Ok(Coin {
+            amount,
+            denom: denom.to_string(),
         })
     }
 
@@ -256,8 +577,7 @@ impl Contact {
     ) -> Result<SimulateResponse, CosmosGrpcError> {
         let our_address = private_key.to_address(&self.chain_prefix).unwrap();
         let fee_amount = fee_amount.unwrap_or_default();
-        let mut txrpc =
-            timeout(self.get_timeout(), TxServiceClient::connect(self.get_url())).await??;
+        let mut txrpc = grpc_client!(self, TxServiceClient);
 
         let fee_obj = Fee {
             amount: fee_amount.to_vec(),
@@ -284,11 +604,12 @@ impl Contact {
     /// A utility function that creates a one to one simple Coin transfer
     /// and sends it from the provided private key, waiting the configured
     /// amount of time for the tx to enter the chain, if you do not specify
-    /// a fee the smallest working amount will be selected.
+    /// a fee the smallest working amount will be selected. If enabled with
+    /// `set_check_send_enabled` denoms with sending disabled are rejected before sending
     ///
     /// # Arguments
     ///
-    /// * `coin` - The amount and type of coin you are sending
+    /// * `coin` - The amount and type of coin you are sending, either a single Coin or a set of Coins
     /// * `fee_coin` - A fee amount and coin type to use, pass None to send a zero fee transaction
     /// * `destination` - The target destination address
     /// * `wait_timeout` - An optional amount of time to wait for the transaction to enter the blockchain
@@ -317,7 +638,7 @@ impl Contact {
     /// ```
     pub async fn send_coins(
         &self,
-        coin: Coin,
+        coin: impl Into<Coins>,
         fee_coin: Option<Coin>,
         destination: Address,
         wait_timeout: Option<Duration>,
@@ -325,9 +646,21 @@ impl Contact {
     ) -> Result<TransactionResponse, CosmosGrpcError> {
         trace!("Creating transaction");
         let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let amount: Coins = coin.into();
+        if self.check_send_enabled {
+            let denoms = amount.iter().map(|c| c.denom.clone()).collect();
+            for status in self.query_send_enabled(denoms).await? {
+                if !status.enabled {
+                    return Err(CosmosGrpcError::BadInput(format!(
+                        "Sending {} is disabled on this chain",
+                        status.denom
+                    )));
+                }
+            }
+        }
 
         let send = MsgSend {
-            amount: vec![coin.into()],
+            amount: amount.into(),
             from_address: our_address.to_bech32(&self.chain_prefix).unwrap(),
             to_address: destination.to_bech32(&self.chain_prefix).unwrap(),
         };
@@ -343,6 +676,100 @@ impl Contact {
         .await
     }
 
+    /// Sends coins to many destinations in a single transaction, one MsgSend is created for
+    /// each output and all of them are executed together, useful for payroll or airdrops.
+    /// See `send_coins` for details on the other arguments
+    ///
+    /// # Arguments
+    ///
+    /// * `outputs` - The destinations and the coins each of them should receive
+    /// * `fee_coin` - A fee amount and coin type to use, pass None to send a zero fee transaction
+    /// * `wait_timeout` - An optional amount of time to wait for the transaction to enter the blockchain
+    /// * `private_key` - A private key used to sign and send the transaction
+    pub async fn send_coins_multi(
+        &self,
+        outputs: Vec<(Address, Vec<Coin>)>,
+        fee_coin: Option<Coin>,
+        wait_timeout: Option<Duration>,
+        private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        if outputs.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "No outputs provided to send_coins_multi".to_string(),
+            ));
+        }
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+
+        let mut messages = Vec::new();
+        for (destination, coins) in outputs {
+            let send = MsgSend {
+                amount: coins.into_iter().map(|c| c.into()).collect(),
+                from_address: our_address.to_bech32(&self.chain_prefix).unwrap(),
+                to_address: destination.to_bech32(&self.chain_prefix).unwrap(),
+            };
+            messages.push(Msg::new(MSG_SEND_TYPE_URL, send));
+        }
+        self.send_message(
+            &messages,
+            None,
+            &[fee_coin.unwrap_or_default()],
+            wait_timeout,
+            None,
+            private_key,
+        )
+        .await
+    }
+
+    /// Sends coins using the bank module's MsgMultiSend, which moves coins from all `inputs`
+    /// to all `outputs` atomically. The total of the inputs must equal the total of the outputs
+    /// for every denom, this is checked before submission. Every input address must sign the
+    /// transaction, so with a single `private_key` the only input should be its own address
+    ///
+    /// # Arguments
+    ///
+    /// * `inputs` - The addresses coins are taken from and the amount taken from each
+    /// * `outputs` - The addresses coins are sent to and the amount each receives
+    /// * `fee_coin` - A fee amount and coin type to use, pass None to send a zero fee transaction
+    /// * `wait_timeout` - An optional amount of time to wait for the transaction to enter the blockchain
+    /// * `private_key` - A private key used to sign and send the transaction
+    pub async fn multi_send(
+        &self,
+        inputs: Vec<(Address, Coins)>,
+        outputs: Vec<(Address, Coins)>,
+        fee_coin: Option<Coin>,
+        wait_timeout: Option<Duration>,
+        private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        validate_multi_send(&inputs, &outputs)?;
+
+        let multi_send = MsgMultiSend {
+            inputs: inputs
+                .into_iter()
+                .map(|(address, coins)| Input {
+                    address: address.to_bech32(&self.chain_prefix).unwrap(),
+                    coins: coins.into(),
+                })
+                .collect(),
+            outputs: outputs
+                .into_iter()
+                .map(|(address, coins)| Output {
+                    address: address.to_bech32(&self.chain_prefix).unwrap(),
+                    coins: coins.into(),
+                })
+                .collect(),
+        };
+        let msg = Msg::new(MSG_MULTI_SEND_TYPE_URL, multi_send);
+        self.send_message(
+            &[msg],
+            None,
+            &[fee_coin.unwrap_or_default()],
+            wait_timeout,
+            None,
+            private_key,
+        )
+        .await
+    }
+
     #[cfg(feature = "althea")]
     /// A utility function that executes a microtransaction on the Althea Chain, meant to be used by routers
     /// on Althea networks to pay peers for internet service.
@@ -409,7 +836,8 @@ impl Contact {
 
     /// Utility function that waits for a tx to enter the chain by querying
     /// it's txid, will not exit for timeout time unless the error is known
-    /// and unrecoverable
+    /// and unrecoverable. If the tx enters the chain but fails execution
+    /// `TransactionFailed` is returned with the sdk error populated where possible
     pub async fn wait_for_tx(
         &self,
         response: TransactionResponse,
@@ -422,32 +850,114 @@ impl Contact {
             match status {
                 Ok(status) => {
                     if let Some(res) = status.tx_response {
+                        if res.code != 0 {
+                            return Err(transaction_failed(res, Instant::now() - start));
+                        }
                         return Ok(TransactionResponse(res));
                     }
                 }
                 Err(CosmosGrpcError::RequestError { error }) => match error.code() {
                     TonicCode::NotFound | TonicCode::Unknown | TonicCode::InvalidArgument => {}
                     _ => {
-                        return Err(CosmosGrpcError::TransactionFailed {
-                            tx: response.into(),
-                            time: Instant::now() - start,
-                            sdk_error: None,
-                        });
+                        return Err(transaction_failed(response.into(), Instant::now() - start));
                     }
                 },
                 Err(e) => return Err(e),
             }
             sleep(Duration::from_secs(1)).await;
         }
-        Err(CosmosGrpcError::TransactionFailed {
-            tx: response.into(),
-            time: timeout,
-            sdk_error: None,
-        })
+        Err(transaction_failed(response.into(), timeout))
+    }
+
+    /// Waits for a tx to enter the chain by scanning the transactions of each new block
+    /// for its hash, rather than querying the tx service like `wait_for_tx`. This is more
+    /// reliable on nodes where tx indexing lags behind or is disabled, but it can only tell
+    /// that the tx was included, not whether it executed successfully. Blocks are scanned
+    /// starting from the height in the response if the node reported one, otherwise from the
+    /// block before the latest block so a tx included right after broadcast is not missed,
+    /// returns the height of the block containing the tx
+    pub async fn wait_for_tx_in_block(
+        &self,
+        response: TransactionResponse,
+        timeout: Duration,
+    ) -> Result<u64, CosmosGrpcError> {
+        let start = Instant::now();
+        let txhash = response.0.txhash.to_uppercase();
+        let mut next_height = None;
+        while Instant::now() - start < timeout {
+            let latest = match self.get_chain_status().await? {
+                ChainStatus::Moving { block_height } => block_height,
+                ChainStatus::Syncing => return Err(CosmosGrpcError::NodeNotSynced),
+                ChainStatus::WaitingToStart => return Err(CosmosGrpcError::ChainNotRunning),
+            };
+            let mut height =
+                next_height.unwrap_or_else(|| scan_start_height(response.0.height, latest));
+            while height <= latest {
+                if let Some(block) = self.get_block(height).await? {
+                    if block_contains_tx(&block, &txhash) {
+                        return Ok(height);
+                    }
+                }
+                height += 1;
+            }
+            next_height = Some(height);
+            sleep(Duration::from_secs(1)).await;
+        }
+        Err(transaction_failed(response.into(), timeout))
+    }
+}
+
+/// Picks the first block to scan for a tx, the height the node reported for the tx if it has
+/// one, otherwise the block before the latest as the tx may have landed since broadcast
+fn scan_start_height(response_height: i64, latest: u64) -> u64 {
+    if response_height > 0 {
+        response_height as u64
+    } else {
+        latest.saturating_sub(1).max(1)
+    }
+}
+
+/// Returns true if the block contains a tx with the given uppercase hex hash
+fn block_contains_tx(block: &Block, txhash: &str) -> bool {
+    match &block.data {
+        Some(data) => data.txs.iter().any(|tx| tx_hash(tx) == txhash),
+        None => false,
+    }
+}
+
+/// Returns true if this error is the chain rejecting a transaction because it was
+/// signed with an outdated account sequence
+pub(crate) fn is_sequence_error(error: &CosmosGrpcError) -> bool {
+    matches!(
+        error,
+        CosmosGrpcError::TransactionFailed {
+            sdk_error: Some(SdkErrorCode::ErrInvalidSequence | SdkErrorCode::ErrWrongSequence),
+            ..
+        }
+    )
+}
+
+/// Builds a TransactionFailed error for the given response, identifying the sdk error
+/// from the response code, or failing that from the fee and gas info of the response
+fn transaction_failed(tx: TxResponse, time: Duration) -> CosmosGrpcError {
+    let mut sdk_error = tx.sdk_error_code();
+    if sdk_error.is_none() {
+        sdk_error = match determine_min_fees_and_gas(&tx) {
+            Some(FeeInfo::InsufficientGas { .. }) => Some(SdkErrorCode::ErrOutOfGas),
+            Some(FeeInfo::InsufficientFees { .. }) => Some(SdkErrorCode::ErrInsufficientFee),
+            None => None,
+        };
+    }
+    CosmosGrpcError::TransactionFailed {
+        tx,
+        time,
+        sdk_error,
     }
 }
 
-/// A wrapper for TxResponse with better debug printing
+/// A wrapper for TxResponse with better debug printing and convenience accessors, this is
+/// returned by every method of Contact that sends a transaction. The raw TxResponse is available
+/// through `tx_response` or by converting with `From`
 #[derive(Clone)]
 pub struct TransactionResponse(TxResponse);
 
@@ -500,6 +1010,32 @@ impl TransactionResponse {
     pub fn events(&self) -> Vec<Event> {
         self.0.events.clone()
     }
+
+    pub fn tx_response(&self) -> &TxResponse {
+        &self.0
+    }
+
+    /// Returns the id of the governance proposal created by this transaction, if any,
+    /// from its `submit_proposal` event
+    pub fn proposal_id(&self) -> Option<u64> {
+        find_event_attribute(&self.0, "submit_proposal", "proposal_id")?
+            .parse()
+            .ok()
+    }
+}
+
+impl TxResponseExt for TransactionResponse {
+    fn succeeded(&self) -> bool {
+        self.0.succeeded()
+    }
+
+    fn sdk_error_code(&self) -> Option<SdkErrorCode> {
+        self.0.sdk_error_code()
+    }
+
+    fn gas_efficiency(&self) -> f64 {
+        self.0.gas_efficiency()
+    }
 }
 
 impl Debug for TransactionResponse {
@@ -523,6 +1059,46 @@ impl From<TransactionResponse> for TxResponse {
     }
 }
 
+/// Checks the memo against the MaxMemoCharacters auth param, like the sdk this
+/// compares the length in bytes rather than unicode characters
+fn check_memo_length(memo: &str, max_memo_characters: u64) -> Result<(), CosmosGrpcError> {
+    if memo.len() as u64 > max_memo_characters {
+        return Err(CosmosGrpcError::BadInput(format!(
+            "Memo of length {} exceeds the maximum of {max_memo_characters}",
+            memo.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the inputs and outputs of a MsgMultiSend are non empty and balanced per denom
+fn validate_multi_send(
+    inputs: &[(Address, Coins)],
+    outputs: &[(Address, Coins)],
+) -> Result<(), CosmosGrpcError> {
+    if inputs.is_empty() || outputs.is_empty() {
+        return Err(CosmosGrpcError::BadInput(
+            "MultiSend requires at least one input and one output".to_string(),
+        ));
+    }
+    let total = |entries: &[(Address, Coins)]| -> Result<Coins, CosmosGrpcError> {
+        let mut total = Coins::default();
+        for (_, coins) in entries {
+            total = total
+                .add(coins)
+                .map_err(|e| CosmosGrpcError::BadInput(e.to_string()))?;
+        }
+        Ok(total)
+    };
+    let (input_total, output_total) = (total(inputs)?, total(outputs)?);
+    if input_total != output_total {
+        return Err(CosmosGrpcError::BadInput(format!(
+            "MultiSend inputs {input_total:?} do not equal outputs {output_total:?}"
+        )));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use crate::CosmosPrivateKey;
@@ -531,6 +1107,80 @@ mod tests {
 
     const TIMEOUT: Duration = Duration::from_secs(60);
 
+    #[test]
+    fn test_scan_start_height() {
+        assert_eq!(scan_start_height(42, 50), 42);
+        assert_eq!(scan_start_height(0, 50), 49);
+        assert_eq!(scan_start_height(0, 1), 1);
+        assert_eq!(scan_start_height(0, 0), 1);
+    }
+
+    #[test]
+    fn test_block_contains_tx() {
+        use cosmos_sdk_proto::tendermint::types::Data;
+
+        let tx = b"some tx bytes".to_vec();
+        let txhash = tx_hash(&tx);
+        let block = Block {
+            data: Some(Data {
+                txs: vec![b"other tx".to_vec(), tx],
+            }),
+            ..Default::default()
+        };
+        assert!(block_contains_tx(&block, &txhash));
+        assert!(!block_contains_tx(&block, &tx_hash(b"missing tx")));
+        assert!(!block_contains_tx(&Block::default(), &txhash));
+    }
+
+    #[test]
+    fn test_check_memo_length() {
+        assert!(check_memo_length("", 0).is_ok());
+        assert!(check_memo_length(MEMO, 256).is_ok());
+        assert!(check_memo_length(&"a".repeat(256), 256).is_ok());
+        assert!(matches!(
+            check_memo_length(&"a".repeat(257), 256),
+            Err(CosmosGrpcError::BadInput(_))
+        ));
+        // multi byte characters count by their encoded length
+        assert!(check_memo_length("é", 1).is_err());
+    }
+
+    #[test]
+    fn test_validate_multi_send() {
+        let a = CosmosPrivateKey::from_secret(b"a")
+            .to_address("cosmos")
+            .unwrap();
+        let b = CosmosPrivateKey::from_secret(b"b")
+            .to_address("cosmos")
+            .unwrap();
+        let coins = |amounts: &[(u32, &str)]| {
+            Coins::new(
+                amounts
+                    .iter()
+                    .map(|(amount, denom)| Coin::new((*amount).into(), denom.to_string()))
+                    .collect(),
+            )
+            .unwrap()
+        };
+
+        let inputs = vec![(a, coins(&[(10, "uatom"), (5, "ufoo")]))];
+        let outputs = vec![
+            (a, coins(&[(4, "uatom")])),
+            (b, coins(&[(6, "uatom"), (5, "ufoo")])),
+        ];
+        assert!(validate_multi_send(&inputs, &outputs).is_ok());
+
+        let unbalanced = vec![(b, coins(&[(10, "uatom")]))];
+        assert!(matches!(
+            validate_multi_send(&inputs, &unbalanced),
+            Err(CosmosGrpcError::BadInput(_))
+        ));
+        assert!(matches!(
+            validate_multi_send(&[], &outputs),
+            Err(CosmosGrpcError::BadInput(_))
+        ));
+    }
+
     #[ignore]
     #[actix_rt::test]
     async fn test_send_to_althea() {
@@ -555,4 +1205,102 @@ mod tests {
             .await;
         assert!(res.is_ok())
     }
+    #[test]
+    fn test_transaction_response() {
+        use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{Attribute, StringEvent};
+
+        let tx = TxResponse {
+            txhash: "ABCD".to_string(),
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![StringEvent {
+                    r#type: "submit_proposal".to_string(),
+                    attributes: vec![Attribute {
+                        key: "proposal_id".to_string(),
+                        value: "3".to_string(),
+                    }],
+                }],
+            }],
+            ..Default::default()
+        };
+        let res: TransactionResponse = tx.clone().into();
+        assert!(res.succeeded());
+        assert_eq!(res.txhash(), "ABCD");
+        assert_eq!(res.proposal_id(), Some(3));
+        assert_eq!(res.tx_response(), &tx);
+        assert_eq!(TxResponse::from(res), tx);
+
+        let failed: TransactionResponse = TxResponse {
+            code: 5,
+            ..Default::default()
+        }
+        .into();
+        assert!(!failed.succeeded());
+        assert_eq!(failed.proposal_id(), None);
+    }
+
+    #[test]
+    fn test_transaction_failed() {
+        let failed = |code: u32, codespace: &str, gas_wanted: i64, gas_used: i64| {
+            let tx = TxResponse {
+                code,
+                codespace: codespace.to_string(),
+                gas_wanted,
+                gas_used,
+                ..Default::default()
+            };
+            match transaction_failed(tx, Duration::from_secs(1)) {
+                CosmosGrpcError::TransactionFailed { sdk_error, .. } => sdk_error,
+                e => panic!("Unexpected error {e:?}"),
+            }
+        };
+        assert_eq!(
+            failed(5, "sdk", 100, 50),
+            Some(SdkErrorCode::ErrInsufficientFunds)
+        );
+        assert_eq!(failed(11, "sdk", 100, 150), Some(SdkErrorCode::ErrOutOfGas));
+        // module specific codes are not sdk errors, unless gas gives it away
+        assert_eq!(failed(5, "bank", 100, 50), None);
+        assert_eq!(failed(5, "bank", 100, 150), Some(SdkErrorCode::ErrOutOfGas));
+    }
+
+    #[test]
+    fn test_is_sequence_error() {
+        let tx = TxResponse {
+            code: 32,
+            codespace: "sdk".to_string(),
+            ..Default::default()
+        };
+        assert!(is_sequence_error(&transaction_failed(
+            tx,
+            Duration::from_secs(0)
+        )));
+        let tx = TxResponse {
+            code: 5,
+            codespace: "sdk".to_string(),
+            ..Default::default()
+        };
+        assert!(!is_sequence_error(&transaction_failed(
+            tx,
+            Duration::from_secs(0)
+        )));
+        assert!(!is_sequence_error(&CosmosGrpcError::NoToken));
+    }
+
+    #[test]
+    fn test_fee_for_gas() {
+        let price: Decimal = "0.025000000000000000".parse().unwrap();
+        assert_eq!(fee_for_gas(200_000, price).unwrap(), 5000u32.into());
+        // rounds up partial units
+        assert_eq!(fee_for_gas(200_001, price).unwrap(), 5001u32.into());
+        // typical evm gas prices are far beyond what Decimal multiplication can handle
+        let price: Decimal = 20_000_000_000u64.into();
+        assert_eq!(
+            fee_for_gas(10_000_000, price).unwrap(),
+            200_000_000_000_000_000u64.into()
+        );
+        let negative = Decimal::from_raw_sdk_dec("-1").unwrap();
+        assert!(fee_for_gas(1, negative).is_err());
+    }
 }
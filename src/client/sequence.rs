@@ -0,0 +1,189 @@
+//! Contains SequencedSender, a utility for sending many transactions from one account in rapid succession
+use crate::address::Address;
+use crate::client::send::{is_sequence_error, TransactionResponse};
+use crate::client::Contact;
+use crate::coin::Fee;
+use crate::error::{CosmosGrpcError, SdkErrorCode};
+use crate::msg::Msg;
+use crate::private_key::{MessageArgs, PrivateKey};
+
+/// Sends transactions from a single account while tracking the account sequence locally,
+/// so that many transactions can be sent per block without querying account info for each
+/// one. The sequence is incremented after every successful broadcast and is only refreshed
+/// from the chain if a transaction is rejected with a sequence error. The timeout height of the
+/// cached args eventually passes, when a transaction is rejected for this the args are
+/// refreshed in the same way, keeping the locally tracked sequence.
+///
+/// Transactions are broadcast without waiting for them to enter the chain, use
+/// `Contact::wait_for_tx` on the responses if you need confirmation.
+pub struct SequencedSender {
+    contact: Contact,
+    address: Address,
+    args: MessageArgs,
+}
+
+impl SequencedSender {
+    /// Creates a new SequencedSender, `args` should be up to date, for example
+    /// fetched with `Contact::get_message_args`
+    pub fn new(contact: Contact, address: Address, args: MessageArgs) -> SequencedSender {
+        SequencedSender {
+            contact,
+            address,
+            args,
+        }
+    }
+
+    /// The MessageArgs that will be used for the next transaction
+    pub fn get_args(&self) -> &MessageArgs {
+        &self.args
+    }
+
+    /// Signs and broadcasts `messages` with the cached sequence and the given `fee`, the
+    /// sequence is incremented on success. If the chain rejects the transaction because the
+    /// sequence or timeout height is out of date the args are re-fetched and the transaction
+    /// is sent once more
+    pub async fn send_next(
+        &mut self,
+        messages: &[Msg],
+        fee: Fee,
+        private_key: impl PrivateKey,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        self.args.fee = fee.clone();
+        let res = self
+            .contact
+            .send_message_with_args(messages, None, self.args.clone(), None, private_key.clone())
+            .await;
+        let res = match res {
+            Err(e) if is_stale_args_error(&e) => {
+                warn!(
+                    "Args with sequence {} and timeout height {} for {} are out of date, refreshing",
+                    self.args.sequence, self.args.timeout_height, self.address
+                );
+                let fetched = self
+                    .contact
+                    .get_message_args(self.address, fee, None)
+                    .await?;
+                self.apply_refresh(fetched, &e);
+                self.contact
+                    .send_message_with_args(messages, None, self.args.clone(), None, private_key)
+                    .await
+            }
+            res => res,
+        };
+        self.record_result(&res);
+        res
+    }
+
+    /// Re-fetches the account sequence, along with the rest of the MessageArgs, from the chain
+    pub async fn refresh(&mut self, fee: Fee) -> Result<(), CosmosGrpcError> {
+        self.args = self
+            .contact
+            .get_message_args(self.address, fee, None)
+            .await?;
+        Ok(())
+    }
+
+    /// Replaces the cached args with `fetched` after a transaction was rejected with `error`.
+    /// A transaction rejected for its timeout height did not consume its sequence, which may
+    /// be ahead of the chain's if transactions are still in the mempool, so it is kept
+    fn apply_refresh(&mut self, fetched: MessageArgs, error: &CosmosGrpcError) {
+        let sequence = self.args.sequence;
+        self.args = fetched;
+        if !is_sequence_error(error) {
+            self.args.sequence = self.args.sequence.max(sequence);
+        }
+    }
+
+    /// Advances the sequence if the transaction was accepted
+    fn record_result(&mut self, res: &Result<TransactionResponse, CosmosGrpcError>) {
+        if res.is_ok() {
+            self.args.sequence += 1;
+        }
+    }
+}
+
+/// Returns true if the chain rejected a transaction because its sequence or timeout height
+/// is out of date, both of which are fixed by refreshing the args
+fn is_stale_args_error(error: &CosmosGrpcError) -> bool {
+    is_sequence_error(error)
+        || matches!(
+            error,
+            CosmosGrpcError::TransactionFailed {
+                sdk_error: Some(SdkErrorCode::ErrTxTimeoutHeight),
+                ..
+            }
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::private_key::test_message_args;
+    use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+    use std::time::Duration;
+
+    fn failed(sdk_error: SdkErrorCode) -> CosmosGrpcError {
+        CosmosGrpcError::TransactionFailed {
+            tx: TxResponse::default(),
+            time: Duration::from_secs(0),
+            sdk_error: Some(sdk_error),
+        }
+    }
+
+    fn args(sequence: u64, timeout_height: u64) -> MessageArgs {
+        MessageArgs {
+            sequence,
+            fee: Fee::default(),
+            timeout_height,
+            ..test_message_args()
+        }
+    }
+
+    fn new_sender(args: MessageArgs) -> SequencedSender {
+        let contact =
+            Contact::new("http://localhost:9090", Duration::from_secs(1), "cosmos").unwrap();
+        let address =
+            Address::from_bech32("cosmos1qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqnrql8a".to_string())
+                .unwrap();
+        SequencedSender::new(contact, address, args)
+    }
+
+    #[test]
+    fn test_is_stale_args_error() {
+        assert!(is_stale_args_error(&failed(SdkErrorCode::ErrWrongSequence)));
+        assert!(is_stale_args_error(&failed(
+            SdkErrorCode::ErrTxTimeoutHeight
+        )));
+        assert!(!is_stale_args_error(&failed(
+            SdkErrorCode::ErrInsufficientFunds
+        )));
+        assert!(!is_stale_args_error(&CosmosGrpcError::NoToken));
+    }
+
+    #[test]
+    fn test_sequence_increment() {
+        let mut sender = new_sender(args(5, 100));
+        sender.record_result(&Ok(TxResponse::default().into()));
+        sender.record_result(&Ok(TxResponse::default().into()));
+        assert_eq!(sender.get_args().sequence, 7);
+        sender.record_result(&Err(failed(SdkErrorCode::ErrInsufficientFunds)));
+        assert_eq!(sender.get_args().sequence, 7);
+    }
+
+    #[test]
+    fn test_apply_refresh() {
+        // a sequence error means the local sequence is wrong, the chain's is used
+        let mut sender = new_sender(args(7, 100));
+        sender.apply_refresh(args(3, 250), &failed(SdkErrorCode::ErrWrongSequence));
+        assert_eq!(sender.get_args(), &args(3, 250));
+
+        // after a timeout height error the local sequence is kept, since transactions
+        // still in the mempool are not yet reflected in the chain's
+        let mut sender = new_sender(args(7, 100));
+        sender.apply_refresh(args(3, 250), &failed(SdkErrorCode::ErrTxTimeoutHeight));
+        assert_eq!(sender.get_args(), &args(7, 250));
+        let mut sender = new_sender(args(7, 100));
+        sender.apply_refresh(args(9, 250), &failed(SdkErrorCode::ErrTxTimeoutHeight));
+        assert_eq!(sender.get_args(), &args(9, 250));
+    }
+}
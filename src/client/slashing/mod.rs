@@ -0,0 +1,82 @@
+//! Contains utility functions for interacting with the Cosmos sdk slashing module, which
+//! tracks validator liveness and jails misbehaving validators
+
+use super::send::TransactionResponse;
+use crate::client::type_urls::MSG_UNJAIL_TYPE_URL;
+use crate::error::CosmosGrpcError;
+use crate::{Address, Coin, Contact, Msg, PrivateKey};
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::query_client::QueryClient as SlashingQueryClient;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::Params as SlashingParams;
+use cosmos_sdk_proto::cosmos::slashing::v1beta1::{
+    MsgUnjail, QueryParamsRequest, QuerySigningInfoRequest, ValidatorSigningInfo,
+};
+use std::time::Duration;
+use tokio::time::timeout;
+
+impl Contact {
+    /// Unjails the validator operated by the account derived from `private_key`, the
+    /// validator operator address is derived by adding 'valoper' to the chain prefix
+    pub async fn unjail_validator(
+        &self,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let validator_address = our_address.to_valoper(&self.chain_prefix).map_err(|e| {
+            CosmosGrpcError::BadInput(format!("Could not derive validator address {e}"))
+        })?;
+        let unjail = MsgUnjail {
+            validator_addr: validator_address.to_string(),
+        };
+
+        let msg = Msg::new(MSG_UNJAIL_TYPE_URL, unjail);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Gets the slashing module parameters, such as the signed blocks window and the
+    /// slashing fractions for downtime and double signing
+    pub async fn get_slashing_params(&self) -> Result<SlashingParams, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, SlashingQueryClient);
+
+        let res = timeout(
+            self.get_timeout(),
+            grpc.params(self.query_request(QueryParamsRequest {})),
+        )
+        .await??
+        .into_inner();
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No slashing params returned".to_string(),
+            )),
+        }
+    }
+
+    /// Gets the signing info of a validator given its consensus address (valcons prefix),
+    /// this includes the missed block counter and the time the validator is jailed until.
+    /// The consensus address is derived from the ed25519 consensus key of the validator, not
+    /// from its operator address
+    pub async fn get_signing_info(
+        &self,
+        cons_address: Address,
+    ) -> Result<ValidatorSigningInfo, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, SlashingQueryClient);
+
+        let res = timeout(
+            self.get_timeout(),
+            grpc.signing_info(self.query_request(QuerySigningInfoRequest {
+                cons_address: cons_address.to_string(),
+            })),
+        )
+        .await??
+        .into_inner();
+        match res.val_signing_info {
+            Some(info) => Ok(info),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No signing info returned".to_string(),
+            )),
+        }
+    }
+}
@@ -15,14 +15,21 @@ use cosmos_sdk_proto::cosmos::staking::v1beta1::query_client::QueryClient as Sta
 use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgBeginRedelegate;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::MsgUndelegate;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryDelegationRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryDelegatorUnbondingDelegationsRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryRedelegationsRequest;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorDelegationsRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorRequest;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::QueryValidatorsRequest;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::RedelegationResponse;
+use cosmos_sdk_proto::cosmos::staking::v1beta1::UnbondingDelegation;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::Validator;
 use cosmos_sdk_proto::cosmos::staking::v1beta1::{DelegationResponse, QueryPoolRequest};
 use cosmos_sdk_proto::cosmos::staking::v1beta1::{MsgDelegate, Pool};
+use cosmos_sdk_proto::cosmos::staking::v1beta1::{Params, QueryParamsRequest};
 use num256::Uint256;
 use std::time::Duration;
 use tokio::time::timeout;
+use tonic::Code as GrpcCode;
 
 /// A parsed staking pool response type
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,36 +50,89 @@ impl From<Pool> for StakingPool {
 impl Contact {
     /// Gets info about the staking pool
     pub async fn get_staking_pool_info(&self) -> Result<Pool, CosmosGrpcError> {
-        let mut grpc = timeout(
+        let mut grpc = grpc_client!(self, StakingQueryClient);
+
+        let res = timeout(
             self.get_timeout(),
-            StakingQueryClient::connect(self.url.clone()),
+            grpc.pool(self.query_request(QueryPoolRequest {})),
         )
-        .await??;
-
-        let res = timeout(self.get_timeout(), grpc.pool(QueryPoolRequest {}))
-            .await??
-            .into_inner();
+        .await??
+        .into_inner();
         Ok(res.pool.unwrap())
     }
 
+    /// Gets the parameters of the staking module, such as the unbonding time and
+    /// maximum number of validators
+    pub async fn get_staking_params(&self) -> Result<Params, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, StakingQueryClient);
+
+        let res = timeout(
+            self.get_timeout(),
+            grpc.params(self.query_request(QueryParamsRequest {})),
+        )
+        .await??
+        .into_inner();
+        match res.params {
+            Some(params) => Ok(params),
+            None => Err(CosmosGrpcError::BadResponse(
+                "No staking params returned".to_string(),
+            )),
+        }
+    }
+
     /// Gets a list of validators
     pub async fn get_validators_list(
         &self,
         filters: QueryValidatorsRequest,
     ) -> Result<Vec<Validator>, CosmosGrpcError> {
-        let mut grpc = timeout(
+        let mut grpc = grpc_client!(self, StakingQueryClient);
+
+        let res = timeout(
             self.get_timeout(),
-            StakingQueryClient::connect(self.url.clone()),
+            grpc.validators(self.query_request(filters)),
         )
-        .await??;
-
-        let res = timeout(self.get_timeout(), grpc.validators(filters))
-            .await??
-            .into_inner()
-            .validators;
+        .await??
+        .into_inner()
+        .validators;
         Ok(res)
     }
 
+    /// Gets a single validator by its operator (valoper) address, returns None if
+    /// no validator with the given address exists
+    pub async fn get_validator(
+        &self,
+        operator: Address,
+    ) -> Result<Option<Validator>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, StakingQueryClient);
+
+        let res = timeout(
+            self.get_timeout(),
+            grpc.validator(self.query_request(QueryValidatorRequest {
+                validator_addr: operator.to_string(),
+            })),
+        )
+        .await?;
+        match res {
+            Ok(res) => Ok(res.into_inner().validator),
+            Err(e) => match e.code() {
+                GrpcCode::NotFound => Ok(None),
+                _ => Err(CosmosGrpcError::RequestError { error: e }),
+            },
+        }
+    }
+
+    /// Gets the delegation a validator has made to itself, `operator` is the valoper address
+    /// of the validator, the account address of the operator is derived from it
+    pub async fn get_self_delegation(
+        &self,
+        operator: Address,
+    ) -> Result<Option<DelegationResponse>, CosmosGrpcError> {
+        let account = operator
+            .with_prefix(&self.chain_prefix)
+            .map_err(|e| CosmosGrpcError::BadInput(e.to_string()))?;
+        self.get_delegation(operator, account).await
+    }
+
     /// Gets a list of bonded validators
     pub async fn get_active_validators(&self) -> Result<Vec<Validator>, CosmosGrpcError> {
         let req = QueryValidatorsRequest {
@@ -87,18 +147,14 @@ impl Contact {
         &self,
         validator: Address,
     ) -> Result<Vec<DelegationResponse>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            StakingQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, StakingQueryClient);
 
         let res = timeout(
             self.get_timeout(),
-            grpc.validator_delegations(QueryValidatorDelegationsRequest {
+            grpc.validator_delegations(self.query_request(QueryValidatorDelegationsRequest {
                 validator_addr: validator.to_string(),
                 pagination: PAGE,
-            }),
+            })),
         )
         .await??
         .into_inner()
@@ -112,18 +168,14 @@ impl Contact {
         validator: Address,
         delegator: Address,
     ) -> Result<Option<DelegationResponse>, CosmosGrpcError> {
-        let mut grpc = timeout(
-            self.get_timeout(),
-            StakingQueryClient::connect(self.url.clone()),
-        )
-        .await??;
+        let mut grpc = grpc_client!(self, StakingQueryClient);
 
         let res = timeout(
             self.get_timeout(),
-            grpc.delegation(QueryDelegationRequest {
+            grpc.delegation(self.query_request(QueryDelegationRequest {
                 delegator_addr: delegator.to_string(),
                 validator_addr: validator.to_string(),
-            }),
+            })),
         )
         .await??
         .into_inner()
@@ -132,6 +184,54 @@ impl Contact {
         Ok(res)
     }
 
+    /// Gets the pending unbonding delegations of a delegator, each entry includes the
+    /// completion time at which the unbonding tokens become liquid
+    pub async fn get_unbonding_delegations(
+        &self,
+        delegator: Address,
+    ) -> Result<Vec<UnbondingDelegation>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, StakingQueryClient);
+
+        let res = timeout(
+            self.get_timeout(),
+            grpc.delegator_unbonding_delegations(self.query_request(
+                QueryDelegatorUnbondingDelegationsRequest {
+                    delegator_addr: delegator.to_string(),
+                    pagination: PAGE,
+                },
+            )),
+        )
+        .await??
+        .into_inner()
+        .unbonding_responses;
+        Ok(res)
+    }
+
+    /// Gets the pending redelegations of a delegator, optionally filtered by source and
+    /// destination validator. Each entry includes the time the redelegation completes
+    pub async fn get_redelegations(
+        &self,
+        delegator: Address,
+        src: Option<Address>,
+        dst: Option<Address>,
+    ) -> Result<Vec<RedelegationResponse>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, StakingQueryClient);
+
+        let res = timeout(
+            self.get_timeout(),
+            grpc.redelegations(self.query_request(QueryRedelegationsRequest {
+                delegator_addr: delegator.to_string(),
+                src_validator_addr: src.map(|a| a.to_string()).unwrap_or_default(),
+                dst_validator_addr: dst.map(|a| a.to_string()).unwrap_or_default(),
+                pagination: PAGE,
+            })),
+        )
+        .await??
+        .into_inner()
+        .redelegation_responses;
+        Ok(res)
+    }
+
     /// Delegates tokens to a specified bonded validator
     pub async fn delegate_to_validator(
         &self,
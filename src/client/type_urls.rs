@@ -2,13 +2,30 @@
 
 // cosmos-sdk msgs
 pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const MSG_MULTI_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgMultiSend";
 
 pub const MSG_VERIFY_INVARIANT_TYPE_URL: &str = "/cosmos.crisis.v1beta1.MsgVerifyInvariant";
 
 pub const SECP256K1_PUBKEY_TYPE_URL: &str = "/cosmos.crypto.secp256k1.PubKey";
 
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+pub const MSG_GRANT_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgGrant";
+pub const MSG_REVOKE_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgRevoke";
+pub const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+
+pub const MSG_GRANT_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.MsgGrantAllowance";
+pub const MSG_REVOKE_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.MsgRevokeAllowance";
+pub const BASIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+
+pub const MSG_CREATE_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreateVestingAccount";
+pub const MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL: &str =
+    "/cosmos.vesting.v1beta1.MsgCreatePeriodicVestingAccount";
+
 pub const MSG_FUND_COMMUNITY_POOL_TYPE_URL: &str =
     "/cosmos.distribution.v1beta1.MsgFundCommunityPool";
+pub const MSG_SET_WITHDRAW_ADDRESS_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.MsgSetWithdrawAddress";
 pub const MSG_WITHDRAW_DELEGATOR_REWARD_TYPE_URL: &str =
     "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward";
 pub const MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL: &str =
@@ -16,11 +33,16 @@ pub const MSG_WITHDRAW_VALIDATOR_COMMISSION_TYPE_URL: &str =
 
 pub const MSG_SUBMIT_PROPOSAL_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgSubmitProposal";
 pub const MSG_VOTE_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgVote";
+pub const MSG_DEPOSIT_TYPE_URL: &str = "/cosmos.gov.v1beta1.MsgDeposit";
+pub const MSG_DEPOSIT_V1_TYPE_URL: &str = "/cosmos.gov.v1.MsgDeposit";
+pub const MSG_VOTE_WEIGHTED_TYPE_URL: &str = "/cosmos.gov.v1.MsgVoteWeighted";
 
 pub const MSG_BEGIN_REDELEGATE_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgBeginRedelegate";
 pub const MSG_DELEGATE_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgDelegate";
 pub const MSG_UNDELEGATE_TYPE_URL: &str = "/cosmos.staking.v1beta1.MsgUndelegate";
 
+pub const MSG_UNJAIL_TYPE_URL: &str = "/cosmos.slashing.v1beta1.MsgUnjail";
+
 // ibc msgs
 pub const MSG_TRANSFER_TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
 
@@ -31,9 +53,15 @@ pub const PARAMETER_CHANGE_PROPOSAL_TYPE_URL: &str =
     "/cosmos.params.v1beta1.ParameterChangeProposal";
 pub const SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL: &str =
     "/cosmos.upgrade.v1beta1.SoftwareUpgradeProposal";
+pub const COMMUNITY_POOL_SPEND_PROPOSAL_TYPE_URL: &str =
+    "/cosmos.distribution.v1beta1.CommunityPoolSpendProposal";
 
 // althea msgs
 pub const MSG_MICROTX_TYPE_URL: &str = "/althea.microtx.v1.MsgMicrotx";
+#[cfg(feature = "althea")]
+pub const MSG_CONVERT_COIN_TYPE_URL: &str = "/canto.erc20.v1.MsgConvertCoin";
+#[cfg(feature = "althea")]
+pub const MSG_CONVERT_ERC20_TYPE_URL: &str = "/canto.erc20.v1.MsgConvertERC20";
 
 // canto proposals
 pub const REGISTER_COIN_PROPOSAL_TYPE_URL: &str = "/canto.erc20.v1.RegisterCoinProposal";
@@ -1,13 +1,19 @@
 use crate::address::Address;
+use crate::coin::{sum_coins, Coin};
 use crate::error::CosmosGrpcError;
 use bytes::BytesMut;
 use cosmos_sdk_proto::cosmos::auth::v1beta1::{BaseAccount as ProtoBaseAccount, ModuleAccount};
+use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
+use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
 use cosmos_sdk_proto::cosmos::vesting::v1beta1::{
-    ContinuousVestingAccount, DelayedVestingAccount, PeriodicVestingAccount, PermanentLockedAccount,
+    BaseVestingAccount, ContinuousVestingAccount, DelayedVestingAccount, PeriodicVestingAccount,
+    PermanentLockedAccount,
 };
 use cosmos_sdk_proto::tendermint::types::Block;
+use num256::Uint256;
 use prost::Message;
 use prost_types::Any;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// This struct represents the status of a Cosmos chain, instead of just getting the
 /// latest block height we mandate that chain status is used, this allows callers to
@@ -96,6 +102,115 @@ impl AccountType {
             (Err(e), _, _, _, _, _) => Err(CosmosGrpcError::DecodeError { error: e }),
         }
     }
+
+    /// Returns the vesting data shared by all vesting account types, None for other accounts
+    fn base_vesting_account(&self) -> Option<&BaseVestingAccount> {
+        match self {
+            AccountType::PeriodicVestingAccount(a) => a.base_vesting_account.as_ref(),
+            AccountType::ContinuousVestingAccount(a) => a.base_vesting_account.as_ref(),
+            AccountType::DelayedVestingAccount(a) => a.base_vesting_account.as_ref(),
+            AccountType::PermenantLockedAccount(a) => a.base_vesting_account.as_ref(),
+            AccountType::ProtoBaseAccount(_) | AccountType::ModuleAccount(_) => None,
+        }
+    }
+
+    /// For periodic and delayed vesting accounts returns the unix time in seconds at which each
+    /// tranche of coins vests along with those coins. Continuous vesting accounts vest linearly
+    /// and permanently locked accounts never vest, so None is returned for them as well as for
+    /// accounts that do not vest
+    pub fn vesting_periods(&self) -> Option<Vec<(i64, Vec<Coin>)>> {
+        match self {
+            AccountType::PeriodicVestingAccount(a) => {
+                let mut time = a.start_time;
+                Some(
+                    a.vesting_periods
+                        .iter()
+                        .map(|period| {
+                            time += period.length;
+                            (time, to_coins(&period.amount))
+                        })
+                        .collect(),
+                )
+            }
+            AccountType::DelayedVestingAccount(_) => {
+                let base = self.base_vesting_account()?;
+                Some(vec![(base.end_time, to_coins(&base.original_vesting))])
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the coins of the original vesting amount that have vested at `time`, following
+    /// the same rules as the Cosmos SDK. Returns None for accounts that do not vest
+    pub fn vested_amount_at(&self, time: SystemTime) -> Option<Vec<Coin>> {
+        let base = self.base_vesting_account()?;
+        let now = unix_seconds(time);
+        match self {
+            AccountType::ContinuousVestingAccount(a) => {
+                let original = to_coins(&base.original_vesting);
+                if now <= a.start_time {
+                    Some(Vec::new())
+                } else if now >= base.end_time {
+                    Some(original)
+                } else {
+                    let elapsed: Uint256 = ((now - a.start_time) as u64).into();
+                    let duration: Uint256 = ((base.end_time - a.start_time) as u64).into();
+                    Some(
+                        original
+                            .into_iter()
+                            .map(|c| Coin::new(c.amount * elapsed / duration, c.denom))
+                            .filter(|c| c.amount != 0u8.into())
+                            .collect(),
+                    )
+                }
+            }
+            AccountType::PeriodicVestingAccount(_) | AccountType::DelayedVestingAccount(_) => {
+                let vested: Vec<Coin> = self
+                    .vesting_periods()?
+                    .into_iter()
+                    .filter(|(end, _)| *end <= now)
+                    .flat_map(|(_, coins)| coins)
+                    .collect();
+                sum_coins(&vested).ok()
+            }
+            _ => Some(Vec::new()),
+        }
+    }
+
+    /// Returns the coins of the original vesting amount that are still locked at `time`.
+    /// Returns None for accounts that do not vest
+    pub fn locked_amount_at(&self, time: SystemTime) -> Option<Vec<Coin>> {
+        let original = to_coins(&self.base_vesting_account()?.original_vesting);
+        let vested = self.vested_amount_at(time)?;
+        Some(
+            original
+                .into_iter()
+                .filter_map(|c| {
+                    let vested = vested
+                        .iter()
+                        .find(|v| v.denom == c.denom)
+                        .map(|v| v.amount)
+                        .unwrap_or_else(|| 0u8.into());
+                    if c.amount > vested {
+                        Some(Coin::new(c.amount - vested, c.denom))
+                    } else {
+                        None
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+fn to_coins(coins: &[ProtoCoin]) -> Vec<Coin> {
+    coins.iter().cloned().map(Coin::from).collect()
+}
+
+fn unix_seconds(time: SystemTime) -> i64 {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs() as i64,
+        Err(e) => -(e.duration().as_secs() as i64),
+    }
 }
 
 /// This is a parsed and validated version of the Cosmos base account proto
@@ -202,5 +317,126 @@ pub struct BlockParams {
     pub max_gas: Option<u64>,
 }
 
+/// One page of the results of a transaction search by events, `page` and `limit` are the
+/// values used for this page, `total` is the number of matching transactions across all pages
+#[derive(Debug, Clone)]
+pub struct TxSearchResult {
+    pub tx_responses: Vec<TxResponse>,
+    pub total: u64,
+    pub page: u64,
+    pub limit: u64,
+}
+
+impl TxSearchResult {
+    /// Returns true if there are more pages of results after this one
+    pub fn has_next_page(&self) -> bool {
+        self.page.saturating_mul(self.limit) < self.total
+    }
+}
+
+/// Identifies an ibc packet by the port and channel it was sent on and its sequence
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IbcPacketId {
+    pub port_id: String,
+    pub channel_id: String,
+    pub sequence: u64,
+}
+
+/// The final outcome of an ibc transfer from the point of view of the sending chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IbcTransferStatus {
+    /// The destination chain received and successfully processed the packet, and its
+    /// acknowledgement has been relayed back
+    Success,
+    /// The destination chain received the packet but failed to process it, for example due
+    /// to an invalid receiver, the error acknowledgement has been relayed back and the sender
+    /// has been refunded
+    ErrorAck,
+    /// The packet was not received before its timeout and the sender has been refunded
+    TimedOut,
+}
+
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::cosmos::vesting::v1beta1::Period;
+    use std::time::Duration;
+
+    fn proto_coins(amount: u64) -> Vec<ProtoCoin> {
+        vec![ProtoCoin {
+            denom: "uatom".to_string(),
+            amount: amount.to_string(),
+        }]
+    }
+
+    fn base_vesting(amount: u64, end_time: i64) -> Option<BaseVestingAccount> {
+        Some(BaseVestingAccount {
+            base_account: None,
+            original_vesting: proto_coins(amount),
+            delegated_free: Vec::new(),
+            delegated_vesting: Vec::new(),
+            end_time,
+        })
+    }
+
+    #[test]
+    fn test_vesting_schedules() {
+        let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+        let uatom = |amount: u64| vec![Coin::new(amount.into(), "uatom".to_string())];
+
+        let continuous = AccountType::ContinuousVestingAccount(ContinuousVestingAccount {
+            base_vesting_account: base_vesting(1000, 200),
+            start_time: 100,
+        });
+        assert_eq!(continuous.vesting_periods(), None);
+        assert_eq!(continuous.vested_amount_at(at(50)), Some(Vec::new()));
+        assert_eq!(continuous.vested_amount_at(at(150)), Some(uatom(500)));
+        assert_eq!(continuous.locked_amount_at(at(175)), Some(uatom(250)));
+        assert_eq!(continuous.locked_amount_at(at(300)), Some(Vec::new()));
+
+        let delayed = AccountType::DelayedVestingAccount(DelayedVestingAccount {
+            base_vesting_account: base_vesting(1000, 200),
+        });
+        assert_eq!(delayed.vesting_periods(), Some(vec![(200, uatom(1000))]));
+        assert_eq!(delayed.locked_amount_at(at(199)), Some(uatom(1000)));
+        assert_eq!(delayed.vested_amount_at(at(200)), Some(uatom(1000)));
+
+        let periodic = AccountType::PeriodicVestingAccount(PeriodicVestingAccount {
+            base_vesting_account: base_vesting(300, 130),
+            start_time: 100,
+            vesting_periods: vec![
+                Period {
+                    length: 10,
+                    amount: proto_coins(100),
+                },
+                Period {
+                    length: 20,
+                    amount: proto_coins(200),
+                },
+            ],
+        });
+        assert_eq!(
+            periodic.vesting_periods(),
+            Some(vec![(110, uatom(100)), (130, uatom(200))])
+        );
+        assert_eq!(periodic.vested_amount_at(at(115)), Some(uatom(100)));
+        assert_eq!(periodic.locked_amount_at(at(115)), Some(uatom(200)));
+
+        let base = AccountType::ProtoBaseAccount(ProtoBaseAccount::default());
+        assert_eq!(base.vested_amount_at(at(115)), None);
+    }
+
+    #[test]
+    fn test_tx_search_pages() {
+        let page = |page, total| TxSearchResult {
+            tx_responses: Vec::new(),
+            total,
+            page,
+            limit: 100,
+        };
+        assert!(page(1, 101).has_next_page());
+        assert!(!page(1, 100).has_next_page());
+        assert!(!page(2, 150).has_next_page());
+        assert!(!page(1, 0).has_next_page());
+    }
+}
@@ -0,0 +1,37 @@
+//! Contains utility functions for querying the Cosmos sdk upgrade module, which coordinates
+//! software upgrades at a specific height
+
+use crate::error::CosmosGrpcError;
+use crate::Contact;
+use cosmos_sdk_proto::cosmos::upgrade::v1beta1::query_client::QueryClient as UpgradeQueryClient;
+use cosmos_sdk_proto::cosmos::upgrade::v1beta1::{
+    Plan, QueryAppliedPlanRequest, QueryCurrentPlanRequest,
+};
+use tokio::time::timeout;
+
+impl Contact {
+    /// Gets the currently scheduled upgrade plan, returns None if no upgrade is planned
+    pub async fn get_current_upgrade_plan(&self) -> Result<Option<Plan>, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, UpgradeQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.current_plan(self.query_request(QueryCurrentPlanRequest {})),
+        )
+        .await??
+        .into_inner();
+        Ok(res.plan)
+    }
+
+    /// Gets the height at which the upgrade with the given `name` was applied, the chain
+    /// returns zero if no upgrade by that name has been applied
+    pub async fn get_applied_upgrade_height(&self, name: String) -> Result<u64, CosmosGrpcError> {
+        let mut grpc = grpc_client!(self, UpgradeQueryClient);
+        let res = timeout(
+            self.get_timeout(),
+            grpc.applied_plan(self.query_request(QueryAppliedPlanRequest { name })),
+        )
+        .await??
+        .into_inner();
+        Ok(res.height as u64)
+    }
+}
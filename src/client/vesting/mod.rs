@@ -0,0 +1,90 @@
+//! Contains utility functions for creating vesting accounts with the Cosmos sdk vesting module
+
+use super::send::TransactionResponse;
+use crate::client::type_urls::{
+    MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL, MSG_CREATE_VESTING_ACCOUNT_TYPE_URL,
+};
+use crate::error::CosmosGrpcError;
+use crate::{Address, Coin, Contact, Msg, PrivateKey};
+use cosmos_sdk_proto::cosmos::vesting::v1beta1::{
+    MsgCreatePeriodicVestingAccount, MsgCreateVestingAccount, Period,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+impl Contact {
+    /// Creates a new vesting account at `to` funded with `amount` from the account derived
+    /// from `private_key`. If `delayed` is true all coins vest at `end_time`, otherwise they
+    /// vest continuously from the time the account is created until `end_time`. The `to`
+    /// account must not already exist
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_vesting_account(
+        &self,
+        to: Address,
+        amount: Vec<Coin>,
+        end_time: SystemTime,
+        delayed: bool,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let create = MsgCreateVestingAccount {
+            from_address: our_address.to_string(),
+            to_address: to.to_string(),
+            amount: amount.into_iter().map(|c| c.into()).collect(),
+            end_time: unix_seconds(end_time)?,
+            delayed,
+        };
+
+        let msg = Msg::new(MSG_CREATE_VESTING_ACCOUNT_TYPE_URL, create);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+
+    /// Creates a new periodic vesting account at `to` funded from the account derived from
+    /// `private_key`. Each of the `periods` is a duration and the coins that vest at the end
+    /// of it, each period starts when the previous one ends with the first starting at
+    /// `start_time`. The account is funded with the sum of the coins of all periods
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_periodic_vesting_account(
+        &self,
+        to: Address,
+        start_time: SystemTime,
+        periods: Vec<(Duration, Vec<Coin>)>,
+        fee: Coin,
+        private_key: impl PrivateKey,
+        wait_timeout: Option<Duration>,
+    ) -> Result<TransactionResponse, CosmosGrpcError> {
+        if periods.is_empty() {
+            return Err(CosmosGrpcError::BadInput(
+                "No vesting periods provided".to_string(),
+            ));
+        }
+        let our_address = private_key.to_address(&self.chain_prefix).unwrap();
+        let create = MsgCreatePeriodicVestingAccount {
+            from_address: our_address.to_string(),
+            to_address: to.to_string(),
+            start_time: unix_seconds(start_time)?,
+            vesting_periods: periods
+                .into_iter()
+                .map(|(length, amount)| Period {
+                    length: length.as_secs() as i64,
+                    amount: amount.into_iter().map(|c| c.into()).collect(),
+                })
+                .collect(),
+        };
+
+        let msg = Msg::new(MSG_CREATE_PERIODIC_VESTING_ACCOUNT_TYPE_URL, create);
+        self.send_message(&[msg], None, &[fee], wait_timeout, None, private_key)
+            .await
+    }
+}
+
+fn unix_seconds(time: SystemTime) -> Result<i64, CosmosGrpcError> {
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(d) => Ok(d.as_secs() as i64),
+        Err(_) => Err(CosmosGrpcError::BadInput(
+            "Vesting times must be after the unix epoch".to_string(),
+        )),
+    }
+}
@@ -1,8 +1,11 @@
 use crate::address::Address;
+use crate::error::CoinError;
 use cosmos_sdk_proto::cosmos::base::v1beta1::Coin as ProtoCoin;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::Fee as ProtoFee;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::Tip as ProtoTip;
 use num256::Uint256;
+use num_traits::{CheckedAdd, CheckedSub};
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
@@ -11,10 +14,51 @@ use std::str::FromStr;
 /// validation and provide a generally nicer interface
 #[derive(Serialize, Debug, Default, Clone, Deserialize, Eq, PartialEq, Hash)]
 pub struct Coin {
+    #[serde(with = "amount_string")]
     pub amount: Uint256,
     pub denom: String,
 }
 
+/// Serializes coin amounts as decimal strings like the Cosmos SDK does, since amounts
+/// easily exceed the safe integer range of JSON numbers. Both strings and numbers are
+/// accepted when deserializing
+mod amount_string {
+    use num256::Uint256;
+    use serde::de::{self, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::fmt;
+
+    pub fn serialize<S: Serializer>(amount: &Uint256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&amount.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Uint256, D::Error> {
+        deserializer.deserialize_any(AmountVisitor)
+    }
+
+    struct AmountVisitor;
+
+    impl<'de> Visitor<'de> for AmountVisitor {
+        type Value = Uint256;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a non negative integer amount as a string or number")
+        }
+
+        fn visit_str<E: de::Error>(self, value: &str) -> Result<Uint256, E> {
+            value.parse().map_err(E::custom)
+        }
+
+        fn visit_u64<E: de::Error>(self, value: u64) -> Result<Uint256, E> {
+            Ok(value.into())
+        }
+
+        fn visit_u128<E: de::Error>(self, value: u128) -> Result<Uint256, E> {
+            Ok(value.into())
+        }
+    }
+}
+
 impl fmt::Display for Coin {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}{}", self.amount, self.denom)
@@ -32,24 +76,60 @@ impl TryFrom<&str> for Coin {
 impl FromStr for Coin {
     type Err = String;
 
+    /// Parses a coin in the form "100uatom", the amount must be an integer
+    /// and the denom must be valid according to the Cosmos SDK rules
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         let value = value.trim();
-        let mut split_idx = 0;
-        for (idx, char) in value.char_indices() {
-            if char.is_alphabetic() {
-                split_idx = idx;
-                break;
-            }
-        }
+        let split_idx = value
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(value.len());
         let (amount, denom) = value.split_at(split_idx);
+        let denom = denom.trim();
+        if amount.is_empty() {
+            return Err(format!("Coin {value:?} is missing an amount"));
+        }
+        if denom.is_empty() {
+            return Err(format!("Coin {value:?} is missing a denom"));
+        }
+        validate_denom(denom)?;
         match amount.parse() {
             Ok(v) => Ok(Coin {
                 amount: v,
                 denom: denom.to_string(),
             }),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(format!("Invalid amount {amount} in coin {value:?}: {e}")),
+        }
+    }
+}
+
+/// Checks a denom against the Cosmos SDK denom format `[a-zA-Z][a-zA-Z0-9/:._-]{2,127}`,
+/// IBC denoms must additionally take the form `ibc/<hash>` where hash is 64 hex characters.
+/// Useful for checking user input before it's sent to a node which would reject it
+pub fn validate_denom(denom: &str) -> Result<(), String> {
+    if denom.len() < 3 || denom.len() > 128 {
+        return Err(format!(
+            "Invalid denom {denom:?}, must be between 3 and 128 characters long"
+        ));
+    }
+    if !denom.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return Err(format!("Invalid denom {denom:?}, must start with a letter"));
+    }
+    if let Some(c) = denom
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "/:._-".contains(*c)))
+    {
+        return Err(format!(
+            "Invalid denom {denom:?}, contains invalid character {c:?}"
+        ));
+    }
+    if let Some(hash) = denom.strip_prefix("ibc/") {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format!(
+                "Invalid IBC denom {denom:?}, must be ibc/ followed by a 64 character hex hash"
+            ));
         }
     }
+    Ok(())
 }
 
 impl Coin {
@@ -57,6 +137,36 @@ impl Coin {
         Coin { amount, denom }
     }
 
+    /// Adds two coins of the same denom, returning an error if the denoms differ
+    /// or the result would overflow
+    pub fn checked_add(&self, other: &Coin) -> Result<Coin, String> {
+        if self.denom != other.denom {
+            return Err(format!(
+                "Can not add {} to {}, denoms do not match",
+                other, self
+            ));
+        }
+        match self.amount.checked_add(&other.amount) {
+            Some(amount) => Ok(Coin::new(amount, self.denom.clone())),
+            None => Err(format!("Overflow adding {other} to {self}")),
+        }
+    }
+
+    /// Subtracts `other` from this coin, returning an error if the denoms differ
+    /// or `other` is larger than this coin
+    pub fn checked_sub(&self, other: &Coin) -> Result<Coin, String> {
+        if self.denom != other.denom {
+            return Err(format!(
+                "Can not subtract {} from {}, denoms do not match",
+                other, self
+            ));
+        }
+        match self.amount.checked_sub(&other.amount) {
+            Some(amount) => Ok(Coin::new(amount, self.denom.clone())),
+            None => Err(format!("Underflow subtracting {other} from {self}")),
+        }
+    }
+
     /// utility function to display a list of coins
     pub fn display_list(input: &[Coin]) -> String {
         let mut out = String::new();
@@ -85,6 +195,141 @@ impl From<Coin> for ProtoCoin {
     }
 }
 
+/// Sums a list of coins into one total per denom, sorted by denom. Returns an error
+/// rather than panicking if any total would overflow
+pub fn sum_coins(coins: &[Coin]) -> Result<Vec<Coin>, String> {
+    let mut totals: BTreeMap<&str, Coin> = BTreeMap::new();
+    for coin in coins {
+        let total = match totals.get(coin.denom.as_str()) {
+            Some(existing) => existing.checked_add(coin)?,
+            None => coin.clone(),
+        };
+        totals.insert(&coin.denom, total);
+    }
+    Ok(totals.into_values().collect())
+}
+
+/// Parses a comma separated list of coins such as "100uatom,250stake", as accepted by the
+/// Cosmos SDK cli. The result is sorted by denom and an error is returned if a denom appears
+/// more than once. An empty string parses to an empty list
+pub fn parse_coins(s: &str) -> Result<Vec<Coin>, String> {
+    if s.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut coins = s
+        .split(',')
+        .map(Coin::from_str)
+        .collect::<Result<Vec<Coin>, String>>()?;
+    coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+    for pair in coins.windows(2) {
+        if pair[0].denom == pair[1].denom {
+            return Err(format!("Duplicate denom {} in {}", pair[0].denom, s));
+        }
+    }
+    Ok(coins)
+}
+
+/// Coins is a set of Coin values as used by the Cosmos SDK (`sdk.Coins`), sorted by denom
+/// with no duplicate denoms and no zero amounts. Nodes reject coin sets that break these rules
+/// so building messages from Coins rather than a bare Vec<Coin> prevents malformed transactions
+#[derive(Serialize, Debug, Default, Clone, Deserialize, Eq, PartialEq, Hash)]
+#[serde(try_from = "Vec<Coin>", into = "Vec<Coin>")]
+pub struct Coins(Vec<Coin>);
+
+impl Coins {
+    /// Creates a coin set from the provided coins, sorting them by denom. Returns
+    /// an error if a denom appears more than once or any amount is zero
+    pub fn new(mut coins: Vec<Coin>) -> Result<Coins, CoinError> {
+        coins.sort_by(|a, b| a.denom.cmp(&b.denom));
+        for (i, coin) in coins.iter().enumerate() {
+            if coin.amount == 0u8.into() {
+                return Err(CoinError::ZeroAmount(coin.denom.clone()));
+            }
+            if i > 0 && coins[i - 1].denom == coin.denom {
+                return Err(CoinError::DuplicateDenom(coin.denom.clone()));
+            }
+        }
+        Ok(Coins(coins))
+    }
+
+    /// Adds two coin sets together, summing amounts of matching denoms
+    pub fn add(&self, other: &Coins) -> Result<Coins, CoinError> {
+        let mut totals: BTreeMap<String, Uint256> = BTreeMap::new();
+        for coin in self.iter().chain(other.iter()) {
+            let total = totals
+                .entry(coin.denom.clone())
+                .or_insert_with(|| 0u8.into());
+            *total = total
+                .checked_add(&coin.amount)
+                .ok_or_else(|| CoinError::Overflow(coin.denom.clone()))?;
+        }
+        Ok(Coins(
+            totals
+                .into_iter()
+                .map(|(denom, amount)| Coin::new(amount, denom))
+                .collect(),
+        ))
+    }
+
+    /// Subtracts `other` from this coin set, denoms that reach zero are removed. Returns
+    /// an error if this set does not hold at least as much of every denom in `other`
+    pub fn sub(&self, other: &Coins) -> Result<Coins, CoinError> {
+        let mut result = self.0.clone();
+        for coin in other.iter() {
+            let existing = result
+                .iter_mut()
+                .find(|c| c.denom == coin.denom)
+                .ok_or_else(|| CoinError::InsufficientFunds(coin.denom.clone()))?;
+            existing.amount = existing
+                .amount
+                .checked_sub(&coin.amount)
+                .ok_or_else(|| CoinError::InsufficientFunds(coin.denom.clone()))?;
+        }
+        result.retain(|c| c.amount != 0u8.into());
+        Ok(Coins(result))
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Coin> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl TryFrom<Vec<Coin>> for Coins {
+    type Error = CoinError;
+
+    fn try_from(value: Vec<Coin>) -> Result<Self, Self::Error> {
+        Coins::new(value)
+    }
+}
+
+impl From<Coin> for Coins {
+    /// A single coin is always a valid set, unless it has a zero amount
+    /// in which case the set is empty
+    fn from(value: Coin) -> Self {
+        if value.amount == 0u8.into() {
+            Coins::default()
+        } else {
+            Coins(vec![value])
+        }
+    }
+}
+
+impl From<Coins> for Vec<Coin> {
+    fn from(value: Coins) -> Self {
+        value.0
+    }
+}
+
+impl From<Coins> for Vec<ProtoCoin> {
+    fn from(value: Coins) -> Self {
+        value.0.into_iter().map(|c| c.into()).collect()
+    }
+}
+
 /// Fee represents everything about a Cosmos transaction fee, including the gas limit
 /// who pays, and how much of an arbitrary number of Coin structs.
 #[derive(Serialize, Debug, Default, Clone, Deserialize, Eq, PartialEq, Hash)]
@@ -188,6 +433,138 @@ mod tests {
     use super::*;
     use crate::{CosmosPrivateKey, PrivateKey};
 
+    #[test]
+    fn test_coin_arithmetic() {
+        let a: Coin = "100footoken".parse().unwrap();
+        let b: Coin = "50footoken".parse().unwrap();
+        let other: Coin = "50bartoken".parse().unwrap();
+        assert_eq!(a.checked_add(&b).unwrap(), "150footoken".parse().unwrap());
+        assert_eq!(a.checked_sub(&b).unwrap(), "50footoken".parse().unwrap());
+        assert!(b.checked_sub(&a).is_err());
+        assert!(a.checked_add(&other).is_err());
+        assert!(a.checked_sub(&other).is_err());
+        let max = Coin::new(Uint256::from_be_bytes(&[0xff; 32]), "footoken".to_string());
+        assert!(max.checked_add(&a).is_err());
+
+        let summed = sum_coins(&[a.clone(), other.clone(), b, other]).unwrap();
+        assert_eq!(
+            summed,
+            vec![
+                "100bartoken".parse().unwrap(),
+                "150footoken".parse().unwrap()
+            ]
+        );
+        assert!(sum_coins(&[max, a]).is_err());
+        assert!(sum_coins(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_coins() {
+        let foo: Coin = "100footoken".parse().unwrap();
+        let bar: Coin = "50bartoken".parse().unwrap();
+        let coins = Coins::new(vec![foo.clone(), bar.clone()]).unwrap();
+        // sorted by denom
+        assert_eq!(
+            coins.iter().cloned().collect::<Vec<Coin>>(),
+            vec![bar.clone(), foo.clone()]
+        );
+        assert_eq!(
+            Coins::new(vec![foo.clone(), foo.clone()]),
+            Err(CoinError::DuplicateDenom("footoken".to_string()))
+        );
+        assert_eq!(
+            Coins::new(vec!["0footoken".parse().unwrap()]),
+            Err(CoinError::ZeroAmount("footoken".to_string()))
+        );
+
+        let doubled = coins.add(&coins).unwrap();
+        assert_eq!(
+            doubled,
+            Coins::new(vec![
+                "200footoken".parse().unwrap(),
+                "100bartoken".parse().unwrap()
+            ])
+            .unwrap()
+        );
+        assert_eq!(doubled.sub(&coins).unwrap(), coins);
+        // denoms reaching zero are dropped
+        assert_eq!(coins.sub(&bar.clone().into()).unwrap(), foo.clone().into());
+        assert_eq!(
+            coins.sub(&doubled),
+            Err(CoinError::InsufficientFunds("bartoken".to_string()))
+        );
+        assert_eq!(
+            coins.sub(&"1baztoken".parse::<Coin>().unwrap().into()),
+            Err(CoinError::InsufficientFunds("baztoken".to_string()))
+        );
+
+        let json = serde_json::to_string(&coins).unwrap();
+        assert_eq!(serde_json::from_str::<Coins>(&json).unwrap(), coins);
+        let duplicated = serde_json::to_string(&vec![foo.clone(), foo]).unwrap();
+        assert!(serde_json::from_str::<Coins>(&duplicated).is_err());
+    }
+
+    #[test]
+    fn test_coin_json() {
+        let payload = r#"{"denom":"uatom","amount":"1000000"}"#;
+        let coin: Coin = serde_json::from_str(payload).unwrap();
+        assert_eq!(coin, "1000000uatom".parse().unwrap());
+        assert_eq!(
+            serde_json::to_string(&coin).unwrap(),
+            r#"{"amount":"1000000","denom":"uatom"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Coin>(r#"{"denom":"uatom","amount":1000000}"#).unwrap(),
+            coin
+        );
+
+        let max = Coin::new(Uint256::from_be_bytes(&[0xff; 32]), "uatom".to_string());
+        let json = serde_json::to_string(&max).unwrap();
+        assert_eq!(serde_json::from_str::<Coin>(&json).unwrap(), max);
+        assert!(serde_json::from_str::<Coin>(r#"{"denom":"uatom","amount":-1}"#).is_err());
+    }
+
+    #[test]
+    fn test_validate_denom() {
+        assert!(validate_denom("uatom").is_ok());
+        assert!(validate_denom("gravity0x7580bFE88Dd3d07947908FAE12d95872a260F2D8").is_ok());
+        assert!(validate_denom("factory/cosmos1abc/foo:bar.baz_qux-1").is_ok());
+        assert!(validate_denom(
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        )
+        .is_ok());
+
+        assert!(validate_denom("").is_err());
+        assert!(validate_denom("ab").is_err());
+        assert!(validate_denom("1atom").is_err());
+        assert!(validate_denom("u atom").is_err());
+        assert!(validate_denom(&"a".repeat(129)).is_err());
+        assert!(validate_denom("ibc/27394FB092").is_err());
+        assert!(validate_denom(
+            "ibc/Z7394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_coins() {
+        assert_eq!(
+            parse_coins("100uatom, 250stake,5bartoken").unwrap(),
+            vec![
+                "5bartoken".parse().unwrap(),
+                "250stake".parse().unwrap(),
+                "100uatom".parse().unwrap()
+            ]
+        );
+        assert_eq!(
+            parse_coins("100uatom").unwrap(),
+            vec!["100uatom".parse().unwrap()]
+        );
+        assert!(parse_coins("").unwrap().is_empty());
+        assert!(parse_coins("100uatom,5uatom").is_err());
+        assert!(parse_coins("100uatom,,5stake").is_err());
+    }
+
     #[test]
     fn test_coin_parse() {
         let _test: Coin = "100footoken".parse().unwrap();
@@ -198,6 +575,25 @@ mod tests {
             .parse()
             .unwrap();
 
+        let parsed: Coin = " 100 uatom ".parse().unwrap();
+        assert_eq!(parsed, Coin::new(100u8.into(), "uatom".to_string()));
+        let _ibc: Coin = "5ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+            .parse()
+            .unwrap();
+
+        let missing_amount = "uatom".parse::<Coin>().unwrap_err();
+        assert!(missing_amount.contains("missing an amount"));
+        let missing_denom = "100".parse::<Coin>().unwrap_err();
+        assert!(missing_denom.contains("missing a denom"));
+        assert!(""
+            .parse::<Coin>()
+            .unwrap_err()
+            .contains("missing an amount"));
+        assert!("100ab".parse::<Coin>().is_err());
+        assert!("1.5uatom".parse::<Coin>().is_err());
+        assert!("100u atom".parse::<Coin>().is_err());
+        assert!("-100uatom".parse::<Coin>().is_err());
+
         let _res = CosmosPrivateKey::from_phrase("swim cereal address police kiwi ship safe raven other place lizard index auction mother arrive sad void real library upgrade chase frequent bike diesel", "").unwrap();
     }
 }
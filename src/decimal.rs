@@ -3,7 +3,10 @@
 //!
 //! [1]: https://pkg.go.dev/github.com/cosmos/cosmos-sdk/types#Dec
 
+use num256::Uint256;
+use num_traits::ToPrimitive;
 use rust_decimal::Error as DecimalLibraryError;
+use rust_decimal::RoundingStrategy;
 use std::{
     convert::{TryFrom, TryInto},
     fmt::{self, Debug, Display},
@@ -14,6 +17,9 @@ use std::{
 pub enum DecimalError {
     ExcessivePrecision,
     InvalidPrecision,
+    DivisionByZero,
+    Overflow,
+    InvalidRawValue(String),
     DecimalError(DecimalLibraryError),
 }
 
@@ -26,6 +32,9 @@ impl fmt::Display for DecimalError {
             DecimalError::InvalidPrecision => {
                 write!(f, "Decimal is using an invalid precision must be 0 or 18")
             }
+            DecimalError::DivisionByZero => write!(f, "Attempted to divide a Decimal by zero"),
+            DecimalError::Overflow => write!(f, "Decimal operation overflowed"),
+            DecimalError::InvalidRawValue(v) => write!(f, "Invalid raw sdk.Dec value {v}"),
             DecimalError::DecimalError(v) => {
                 write!(f, "{v:?}")
             }
@@ -70,10 +79,105 @@ impl Decimal {
         let fractional_digits: rust_decimal::Decimal = fractional_digits.into();
         let precision_exp: rust_decimal::Decimal = 10u64.pow(PRECISION).into();
 
-        let mut combined_decimal = (integral_digits * precision_exp) + fractional_digits;
+        let mut combined_decimal = integral_digits
+            .checked_mul(precision_exp)
+            .and_then(|v| v.checked_add(fractional_digits))
+            .ok_or(DecimalError::Overflow)?;
         combined_decimal.set_scale(PRECISION)?;
         Ok(Decimal(combined_decimal))
     }
+
+    /// Parses the raw integer representation of an `sdk.Dec`, this is the format
+    /// used when a Dec is sent over gRPC as bytes or as a string with a custom type,
+    /// the value is a whole number equal to the decimal multiplied by 10^18
+    /// e.g. `"1500000000000000000"` is `1.500000000000000000`
+    pub fn from_raw_sdk_dec(raw: &str) -> Result<Self, DecimalError> {
+        let (negative, digits) = match raw.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, raw),
+        };
+        if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit()) {
+            return Err(DecimalError::InvalidRawValue(raw.to_string()));
+        }
+
+        let split = digits.len().saturating_sub(PRECISION as usize);
+        let (integral, fractional) = digits.split_at(split);
+        let integral: i64 = if integral.is_empty() {
+            0
+        } else {
+            integral.parse().map_err(|_| DecimalError::Overflow)?
+        };
+        // at most 18 digits, always fits
+        let fractional: u64 = fractional.parse().unwrap();
+
+        let value = Decimal::new(integral, fractional)?;
+        if negative {
+            Ok(Decimal(-value.0))
+        } else {
+            Ok(value)
+        }
+    }
+
+    /// Returns the raw integer representation of this decimal as used by `sdk.Dec`
+    /// over gRPC, the inverse of `from_raw_sdk_dec`
+    pub fn to_raw_sdk_dec(&self) -> String {
+        self.0.mantissa().to_string()
+    }
+
+    /// Adds two decimals, returns an error if the result can not be represented
+    /// with 18 digits of precision
+    pub fn checked_add(self, rhs: Decimal) -> Result<Decimal, DecimalError> {
+        match self.0.checked_add(rhs.0) {
+            Some(v) => Decimal::truncate(v),
+            None => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// Subtracts `rhs` from this decimal, returns an error if the result can not be
+    /// represented with 18 digits of precision
+    pub fn checked_sub(self, rhs: Decimal) -> Result<Decimal, DecimalError> {
+        match self.0.checked_sub(rhs.0) {
+            Some(v) => Decimal::truncate(v),
+            None => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// Multiplies two decimals, truncating the result to 18 digits of precision like
+    /// `sdk.Dec.MulTruncate`. Returns an error if the result is out of range
+    pub fn checked_mul(self, rhs: Decimal) -> Result<Decimal, DecimalError> {
+        // trailing zeros are dropped first so that the 18 digit scale of both
+        // operands does not exhaust the precision of the underlying type
+        match self.0.normalize().checked_mul(rhs.0.normalize()) {
+            Some(v) => Decimal::truncate(v),
+            None => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// Divides this decimal by `rhs`, truncating the result to 18 digits of
+    /// precision like `sdk.Dec.QuoTruncate`. Returns an error on division by zero
+    pub fn checked_div(self, rhs: Decimal) -> Result<Decimal, DecimalError> {
+        if rhs.0.is_zero() {
+            return Err(DecimalError::DivisionByZero);
+        }
+        match self.0.normalize().checked_div(rhs.0.normalize()) {
+            Some(v) => Decimal::truncate(v),
+            None => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// Truncates any digits beyond the 18 allowed by `sdk.Dec` and restores
+    /// the fixed 18 digit scale all Decimal values carry. The underlying type can
+    /// only hold about 7.9 * 10^28 in total, so for values beyond roughly 7.9 * 10^10
+    /// the rescale can not reach 18 digits and an Overflow error is returned, rather
+    /// than a value with a smaller scale whose raw sdk.Dec representation would be wrong
+    fn truncate(value: rust_decimal::Decimal) -> Result<Decimal, DecimalError> {
+        let mut value = value.round_dp_with_strategy(PRECISION, RoundingStrategy::ToZero);
+        value.rescale(PRECISION);
+        if value.scale() != PRECISION {
+            return Err(DecimalError::Overflow);
+        }
+        Ok(Decimal(value))
+    }
 }
 
 impl Debug for Decimal {
@@ -101,7 +205,9 @@ impl TryFrom<rust_decimal::Decimal> for Decimal {
         match decimal_value.scale() {
             0 => {
                 let exp: rust_decimal::Decimal = 10u64.pow(PRECISION).into();
-                decimal_value *= exp;
+                decimal_value = decimal_value
+                    .checked_mul(exp)
+                    .ok_or(DecimalError::Overflow)?;
                 decimal_value.set_scale(PRECISION)?;
             }
             PRECISION => (),
@@ -126,13 +232,172 @@ macro_rules! impl_from_primitive_int_for_decimal {
 impl_from_primitive_int_for_decimal!(i8, i16, i32, i64, isize);
 impl_from_primitive_int_for_decimal!(u8, u16, u32, u64, usize);
 
+/// A non-negative `sdk.Dec` of any size, stored as its raw integer representation. Decimal
+/// can only represent values up to roughly 7.9 * 10^10 with 18 digits of precision, which is
+/// far too small for token amounts such as the annual provisions or the community pool of a
+/// chain, this type holds those values exactly and exposes their whole and fractional parts
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub struct WideDecimal(Uint256);
+
+impl WideDecimal {
+    /// Parses the raw integer representation of a non-negative `sdk.Dec`, see
+    /// `Decimal::from_raw_sdk_dec`
+    pub fn from_raw_sdk_dec(raw: &str) -> Result<Self, DecimalError> {
+        if raw.is_empty() || !raw.chars().all(|c| c.is_ascii_digit()) {
+            return Err(DecimalError::InvalidRawValue(raw.to_string()));
+        }
+        match raw.parse() {
+            Ok(v) => Ok(WideDecimal(v)),
+            Err(_) => Err(DecimalError::Overflow),
+        }
+    }
+
+    /// Returns the raw integer representation of this decimal as used by `sdk.Dec`
+    pub fn to_raw_sdk_dec(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Returns the whole number part of this decimal, e.g. `12` for `12.5`
+    pub fn integer(&self) -> Uint256 {
+        self.0 / Self::one()
+    }
+
+    /// Returns the fractional part of this decimal as 18 digits, e.g. `500000000000000000`
+    /// for `12.5`
+    pub fn fraction(&self) -> u64 {
+        // always less than 10^18 and therefore fits
+        (self.0 - self.integer() * Self::one()).to_u64().unwrap()
+    }
+
+    /// Returns this decimal as an f64, this may lose precision
+    pub fn to_f64(&self) -> f64 {
+        // every Uint256 is a valid f64 literal, possibly rounded
+        self.0.to_string().parse::<f64>().unwrap() / 10f64.powi(PRECISION as i32)
+    }
+
+    fn one() -> Uint256 {
+        10u64.pow(PRECISION).into()
+    }
+}
+
+impl Display for WideDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:018}", self.integer(), self.fraction())
+    }
+}
+
+impl TryFrom<WideDecimal> for Decimal {
+    type Error = DecimalError;
+    fn try_from(value: WideDecimal) -> Result<Self, DecimalError> {
+        Decimal::from_raw_sdk_dec(&value.to_raw_sdk_dec())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Decimal;
+    use super::{Decimal, WideDecimal};
 
     #[test]
     fn string_serialization_test() {
         let num = Decimal::from(-1i8);
         assert_eq!(num.to_string(), "-1.000000000000000000")
     }
+
+    #[test]
+    fn arithmetic_test() {
+        let a: Decimal = "1.500000000000000000".parse().unwrap();
+        let b: Decimal = "0.250000000000000000".parse().unwrap();
+        assert_eq!(
+            a.checked_add(b).unwrap().to_string(),
+            "1.750000000000000000"
+        );
+        assert_eq!(
+            b.checked_sub(a).unwrap().to_string(),
+            "-1.250000000000000000"
+        );
+        assert_eq!(
+            a.checked_mul(b).unwrap().to_string(),
+            "0.375000000000000000"
+        );
+        assert_eq!(
+            a.checked_div(b).unwrap().to_string(),
+            "6.000000000000000000"
+        );
+
+        // results beyond 18 digits are truncated rather than rounded
+        let third = Decimal::from(2u8).checked_div(Decimal::from(3u8)).unwrap();
+        assert_eq!(third.to_string(), "0.666666666666666666");
+        let tiny = Decimal::new(0, 1).unwrap();
+        assert_eq!(
+            tiny.checked_mul(b).unwrap().to_string(),
+            "0.000000000000000000"
+        );
+
+        assert!(a.checked_div(Decimal::from(0u8)).is_err());
+    }
+
+    #[test]
+    fn wide_decimal_test() {
+        // 10^13 tokens with a fractional part, well beyond the range of Decimal
+        let raw = "10000000000000500000000000000001";
+        let wide = WideDecimal::from_raw_sdk_dec(raw).unwrap();
+        assert_eq!(wide.integer(), 10_000_000_000_000u64.into());
+        assert_eq!(wide.fraction(), 500_000_000_000_000_001);
+        assert_eq!(wide.to_string(), "10000000000000.500000000000000001");
+        assert_eq!(wide.to_raw_sdk_dec(), raw);
+        assert!((wide.to_f64() - 10_000_000_000_000.5).abs() < 0.001);
+        assert!(Decimal::try_from(wide).is_err());
+
+        let small = WideDecimal::from_raw_sdk_dec("1500000000000000000").unwrap();
+        assert_eq!(small.to_string(), "1.500000000000000000");
+        assert_eq!(
+            Decimal::try_from(small).unwrap(),
+            "1.500000000000000000".parse().unwrap()
+        );
+
+        assert!(WideDecimal::from_raw_sdk_dec("").is_err());
+        assert!(WideDecimal::from_raw_sdk_dec("-1").is_err());
+        assert!(WideDecimal::from_raw_sdk_dec("1.5").is_err());
+    }
+
+    #[test]
+    fn overflow_test() {
+        // 7 * 10^10 is close to the largest value representable with 18 digits of precision
+        let large = Decimal::from(70_000_000_000u64);
+        assert_eq!(large.to_raw_sdk_dec(), "70000000000000000000000000000");
+        // 1.4 * 10^11 and beyond can not be represented, rather than losing precision
+        // and producing a wrong raw sdk.Dec these are errors
+        assert!(large.checked_add(large).is_err());
+        assert!(large
+            .checked_sub(Decimal::from(-70_000_000_000i64))
+            .is_err());
+        assert!(large.checked_mul(Decimal::from(2u8)).is_err());
+        assert!(large
+            .checked_div("0.500000000000000000".parse().unwrap())
+            .is_err());
+        assert!(Decimal::new(100_000_000_000, 0).is_err());
+        assert!("100000000000".parse::<Decimal>().is_err());
+        assert!(Decimal::from_raw_sdk_dec("100000000000000000000000000000").is_err());
+    }
+
+    #[test]
+    fn raw_sdk_dec_test() {
+        let inflation = Decimal::from_raw_sdk_dec("130000000000000000").unwrap();
+        assert_eq!(inflation.to_string(), "0.130000000000000000");
+        let provisions = Decimal::from_raw_sdk_dec("123456789000000000000000001").unwrap();
+        assert_eq!(provisions.to_string(), "123456789.000000000000000001");
+        let negative = Decimal::from_raw_sdk_dec("-1").unwrap();
+        assert_eq!(negative.to_string(), "-0.000000000000000001");
+
+        assert!(Decimal::from_raw_sdk_dec("").is_err());
+        assert_eq!(
+            Decimal::from_raw_sdk_dec("-1500000000000000000")
+                .unwrap()
+                .to_raw_sdk_dec(),
+            "-1500000000000000000"
+        );
+        assert!(Decimal::from_raw_sdk_dec("1.5").is_err());
+        // beyond the range of the underlying type
+        assert!(Decimal::from_raw_sdk_dec("1000000000000000000000000000000000000").is_err());
+    }
 }
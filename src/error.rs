@@ -170,6 +170,8 @@ pub enum AddressError {
     HexDecodeError(ByteDecodeError),
     HexDecodeErrorWrongLength,
     PrefixTooLong(ArrayStringError),
+    InvalidPrefixCharacter(char),
+    EmptyPrefix,
     BytesDecodeErrorWrongLength,
 }
 
@@ -182,6 +184,10 @@ impl fmt::Display for AddressError {
             AddressError::HexDecodeError(val) => write!(f, "HexDecodeError {val}"),
             AddressError::HexDecodeErrorWrongLength => write!(f, "HexDecodeError Wrong Length"),
             AddressError::PrefixTooLong(val) => write!(f, "Prefix too long {val}"),
+            AddressError::InvalidPrefixCharacter(val) => {
+                write!(f, "Invalid prefix character {val:?}")
+            }
+            AddressError::EmptyPrefix => write!(f, "Prefix is empty"),
             AddressError::BytesDecodeErrorWrongLength => write!(f, "BytesDecodeError Wrong Length"),
         }
     }
@@ -236,6 +242,7 @@ pub enum PublicKeyError {
     HexDecodeErrorWrongLength,
     BytesDecodeErrorWrongLength,
     PrefixTooLong(ArrayStringError),
+    CurveError(CurveError),
 }
 
 impl fmt::Display for PublicKeyError {
@@ -251,12 +258,19 @@ impl fmt::Display for PublicKeyError {
             }
             PublicKeyError::HexDecodeErrorWrongLength => write!(f, "HexDecodeError Wrong Length"),
             PublicKeyError::PrefixTooLong(val) => write!(f, "Prefix too long {val}"),
+            PublicKeyError::CurveError(val) => write!(f, "Secp256k1 Error {val}"),
         }
     }
 }
 
 impl std::error::Error for PublicKeyError {}
 
+impl From<CurveError> for PublicKeyError {
+    fn from(error: CurveError) -> Self {
+        PublicKeyError::CurveError(error)
+    }
+}
+
 impl From<ArrayStringError> for PublicKeyError {
     fn from(error: ArrayStringError) -> Self {
         PublicKeyError::PrefixTooLong(error)
@@ -288,6 +302,9 @@ pub enum PrivateKeyError {
     HdWalletError(HdWalletError),
     InvalidMnemonic { error: Bip39Error },
     ZeroPrivateKey,
+    NoSigners,
+    InvalidAminoMsgs(String),
+    InvalidSecretKey(String),
 }
 
 impl fmt::Display for PrivateKeyError {
@@ -304,6 +321,13 @@ impl fmt::Display for PrivateKeyError {
                 write!(f, "Failed to process mnemonic {error:?}")
             }
             PrivateKeyError::ZeroPrivateKey => write!(f, "PrivateKeyError Zero Private Key"),
+            PrivateKeyError::NoSigners => write!(f, "PrivateKeyError No signers provided"),
+            PrivateKeyError::InvalidSecretKey(val) => {
+                write!(f, "PrivateKeyError Invalid secret key {val}")
+            }
+            PrivateKeyError::InvalidAminoMsgs(val) => {
+                write!(f, "PrivateKeyError Invalid amino messages {val}")
+            }
         }
     }
 }
@@ -372,7 +396,7 @@ impl std::error::Error for HdWalletError {}
 /// A BIP39 error.
 #[derive(Clone, PartialEq, Eq)]
 pub enum Bip39Error {
-    /// Mnemonic has a word count that is not a multiple of 6.
+    /// Mnemonic has a word count that is not a multiple of 3.
     BadWordCount(usize),
     /// Mnemonic contains an unknown word.
     UnknownWord(String),
@@ -389,7 +413,7 @@ impl fmt::Display for Bip39Error {
         match *self {
             Bip39Error::BadWordCount(c) => write!(
                 f,
-                "mnemonic has a word count that is not a multiple of 6: {c}",
+                "mnemonic has a word count that is not a multiple of 3: {c}",
             ),
             Bip39Error::UnknownWord(ref w) => {
                 write!(f, "mnemonic contains an unknown word: {w}",)
@@ -428,6 +452,27 @@ impl Display for ArrayStringError {
 
 impl Error for ArrayStringError {}
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoinError {
+    DuplicateDenom(String),
+    ZeroAmount(String),
+    Overflow(String),
+    InsufficientFunds(String),
+}
+
+impl Display for CoinError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            CoinError::DuplicateDenom(denom) => write!(f, "Duplicate denom {denom}"),
+            CoinError::ZeroAmount(denom) => write!(f, "Zero amount of {denom}"),
+            CoinError::Overflow(denom) => write!(f, "Overflow adding {denom}"),
+            CoinError::InsufficientFunds(denom) => write!(f, "Insufficient {denom}"),
+        }
+    }
+}
+
+impl Error for CoinError {}
+
 /// An enum representing Cosmos sdk errors
 /// from the 'sdk' codespace. Each of these errors
 /// maps to a code that we use to identify it in the TxResponse
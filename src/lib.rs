@@ -18,6 +18,7 @@ extern crate log;
 extern crate serde_derive;
 
 pub mod address;
+pub mod amino;
 pub mod client;
 pub mod coin;
 pub mod decimal;
@@ -27,12 +28,16 @@ pub mod msg;
 pub mod private_key;
 pub mod public_key;
 pub mod signature;
+pub mod tx_decode;
 pub mod utils;
 
 pub use address::Address;
+pub use client::send::TransactionResponse;
 pub use client::Contact;
 pub use coin::Coin;
+pub use coin::Coins;
 pub use coin::Fee;
+pub use coin::Tip;
 pub use mnemonic::Mnemonic;
 pub use msg::Msg;
 #[cfg(feature = "ethermint")]
@@ -41,3 +46,4 @@ pub use private_key::MessageArgs;
 pub use private_key::{CosmosPrivateKey, PrivateKey};
 pub use public_key::PublicKey;
 pub use signature::Signature;
+pub use utils::TxResponseExt;
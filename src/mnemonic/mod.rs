@@ -24,10 +24,12 @@ use unicode_normalization::UnicodeNormalization;
 /// mnemonic from all the supported languages. (Languages have to be explicitly enabled using
 /// the Cargo features.)
 ///
-/// Supported number of words are 12, 18 and 24.
+/// Supported number of words are 12, 15, 18, 21 and 24.
 #[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
-pub struct Mnemonic(String);
+pub struct Mnemonic(String, Language);
 // The content of the mnemonic is ensured to be NFKD-normalized UTF-8.
+// The language is kept alongside since some phrases are made up of words that
+// occur in more than one word list.
 
 impl Mnemonic {
     /// Ensure the content of the [Cow] is normalized UTF8.
@@ -77,7 +79,7 @@ impl Mnemonic {
             words.push(language.word_list()[idx]);
         }
 
-        Ok(Mnemonic(words.join(" ")))
+        Ok(Mnemonic(words.join(" "), language))
     }
 
     /// Create a new English [Mnemonic] in from the given entropy.
@@ -86,10 +88,10 @@ impl Mnemonic {
         Mnemonic::from_entropy_in(Language::English, entropy)
     }
 
-    /// Generate a new Mnemonic in the given language.
-    /// For the different supported word counts, see documentation on [Mnemonoc].
+    /// Generate a new Mnemonic in the given language using secure random entropy.
+    /// For the different supported word counts, see documentation on [Mnemonic].
     pub fn generate_in(language: Language, word_count: usize) -> Result<Mnemonic, Bip39Error> {
-        if word_count < 6 || word_count % 6 != 0 || word_count > 24 {
+        if word_count < 12 || word_count % 3 != 0 || word_count > 24 {
             return Err(Bip39Error::BadWordCount(word_count));
         }
 
@@ -101,7 +103,7 @@ impl Mnemonic {
     }
 
     /// Generate a new Mnemonic in English.
-    /// For the different supported word counts, see documentation on [Mnemonoc].
+    /// For the different supported word counts, see documentation on [Mnemonic].
     pub fn generate(word_count: usize) -> Result<Mnemonic, Bip39Error> {
         Mnemonic::generate_in(Language::English, word_count)
     }
@@ -109,7 +111,7 @@ impl Mnemonic {
     /// Static method to validate a mnemonic in a given language.
     pub fn validate_in(language: Language, s: &str) -> Result<(), Bip39Error> {
         let words: Vec<&str> = s.split_whitespace().collect();
-        if words.len() < 6 || words.len() % 6 != 0 || words.len() > 24 {
+        if words.len() < 12 || words.len() % 3 != 0 || words.len() > 24 {
             return Err(Bip39Error::BadWordCount(words.len()));
         }
 
@@ -156,10 +158,10 @@ impl Mnemonic {
     /// returned, containing the possible languages.
     pub fn language_of(s: &str) -> Result<Language, Bip39Error> {
         // First we try wordlists that have guaranteed unique words.
-        let first_word = s.split_whitespace().next().unwrap();
-        if first_word.is_empty() {
-            return Err(Bip39Error::BadWordCount(0));
-        }
+        let first_word = match s.split_whitespace().next() {
+            Some(word) => word,
+            None => return Err(Bip39Error::BadWordCount(0)),
+        };
         for language in Language::all().iter().filter(|l| l.unique_words()) {
             if language.find_word(first_word).is_some() {
                 return Ok(*language);
@@ -190,13 +192,48 @@ impl Mnemonic {
         Err(Bip39Error::AmbiguousWordList(langs))
     }
 
+    /// Validate a mnemonic phrase in any supported language, returning the detected language.
+    ///
+    /// Unlike [Mnemonic::language_of] this verifies the word count and checksum, which is also
+    /// used to settle which language a phrase is in when its words appear in several word lists.
+    /// If the phrase is valid in more than one language [Bip39Error::AmbiguousWordList] is
+    /// returned with the languages it is valid in.
+    pub fn validate(phrase: &str) -> Result<Language, Bip39Error> {
+        let mut cow = Cow::Borrowed(phrase);
+        Mnemonic::normalize_utf8_cow(&mut cow);
+        let phrase = cow.as_ref();
+        match Mnemonic::language_of(phrase) {
+            Ok(language) => {
+                Mnemonic::validate_in(language, phrase)?;
+                Ok(language)
+            }
+            Err(Bip39Error::AmbiguousWordList(langs)) => {
+                let mut first_error = None;
+                let mut valid = Vec::new();
+                for language in langs {
+                    match Mnemonic::validate_in(language, phrase) {
+                        Ok(()) => valid.push(language),
+                        Err(e) => {
+                            first_error.get_or_insert(e);
+                        }
+                    }
+                }
+                match (valid.len(), first_error) {
+                    (1, _) => Ok(valid[0]),
+                    (0, Some(e)) => Err(e),
+                    _ => Err(Bip39Error::AmbiguousWordList(valid)),
+                }
+            }
+            Err(e) => Err(e),
+        }
+    }
+
     /// Parse a mnemonic and detect the language from the enabled languages.
     pub fn parse<'a, S: Into<Cow<'a, str>>>(s: S) -> Result<Mnemonic, Bip39Error> {
         let mut cow = s.into();
         Mnemonic::normalize_utf8_cow(&mut cow);
-        let language = Mnemonic::language_of(cow.as_ref())?;
-        Mnemonic::validate_in(language, cow.as_ref())?;
-        Ok(Mnemonic(cow.into_owned()))
+        let language = Mnemonic::validate(cow.as_ref())?;
+        Ok(Mnemonic(cow.into_owned(), language))
     }
 
     /// Parse a mnemonic in the given language.
@@ -207,7 +244,7 @@ impl Mnemonic {
         let mut cow = s.into();
         Mnemonic::normalize_utf8_cow(&mut cow);
         Mnemonic::validate_in(language, cow.as_ref())?;
-        Ok(Mnemonic(cow.into_owned()))
+        Ok(Mnemonic(cow.into_owned(), language))
     }
 
     /// Get the mnemonic as a [&str].
@@ -215,6 +252,11 @@ impl Mnemonic {
         &self.0
     }
 
+    /// Get the language of the mnemonic.
+    pub fn language(&self) -> Language {
+        self.1
+    }
+
     /// Get the number of words in the mnemonic.
     pub fn word_count(&self) -> usize {
         self.as_str().split_whitespace().count()
@@ -246,12 +288,13 @@ impl Mnemonic {
         seed
     }
 
-    /// Convert the mnemonic back to the entropy used to generate it.
+    /// Convert the mnemonic back to the entropy used to generate it, this is the
+    /// exact inverse of [Mnemonic::from_entropy_in].
     pub fn to_entropy(&self) -> Vec<u8> {
         // We unwrap errors here because this method can only be called on
         // values that were already previously validated.
 
-        let language = Mnemonic::language_of(self.as_str()).unwrap();
+        let language = self.language();
 
         // Preallocate enough space for the longest possible word list
         let mut entropy = Vec::with_capacity(33);
@@ -304,9 +347,17 @@ mod tests {
 
     #[test]
     fn test_bit_counts() {
-        let _ = Mnemonic::generate(12).unwrap();
-        let _ = Mnemonic::generate(18).unwrap();
-        let _ = Mnemonic::generate(24).unwrap();
+        for word_count in [12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(word_count).unwrap();
+            assert_eq!(mnemonic.word_count(), word_count);
+            Mnemonic::parse(mnemonic.as_str()).unwrap();
+        }
+        for word_count in [0, 6, 9, 13, 27] {
+            assert_eq!(
+                Mnemonic::generate_in(Language::Spanish, word_count),
+                Err(Bip39Error::BadWordCount(word_count))
+            );
+        }
     }
 
     #[test]
@@ -317,6 +368,37 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate() {
+        for lang in Language::all() {
+            let m = Mnemonic::generate_in(*lang, 12).unwrap();
+            assert_eq!(Mnemonic::validate(m.as_str()), Ok(*lang));
+        }
+        assert_eq!(
+            Mnemonic::validate(
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage above"
+            ),
+            Ok(Language::English)
+        );
+        assert_eq!(
+            Mnemonic::validate(
+                "letter advice cage absurd amount doctor acoustic avoid getter advice cage above"
+            ),
+            Err(Bip39Error::UnknownWord("getter".to_owned()))
+        );
+        assert_eq!(
+            Mnemonic::validate(
+                "primary advice cage absurd amount doctor acoustic avoid letter advice cage above"
+            ),
+            Err(Bip39Error::InvalidChecksum)
+        );
+        assert_eq!(Mnemonic::validate(""), Err(Bip39Error::BadWordCount(0)));
+        assert_eq!(
+            Mnemonic::language_of("  "),
+            Err(Bip39Error::BadWordCount(0))
+        );
+    }
+
     #[test]
     fn test_vectors_english() {
         // These vectors are tuples of
@@ -498,6 +580,17 @@ mod tests {
             Err(Bip39Error::BadWordCount(11))
         );
 
+        // a valid 9 word phrase, shorter phrases than 12 words are not supported
+        let nine_words = ["abandon"; 9].join(" ");
+        assert_eq!(
+            Mnemonic::validate_in(Language::English, &nine_words),
+            Err(Bip39Error::BadWordCount(9))
+        );
+        assert_eq!(
+            Mnemonic::parse(&nine_words),
+            Err(Bip39Error::BadWordCount(9))
+        );
+
         assert_eq!(
             Mnemonic::parse(
                 "primary advice cage absurd amount doctor acoustic avoid letter advice cage above",
@@ -506,6 +599,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_entropy_round_trip() {
+        for lang in Language::all() {
+            for bits in [128, 160, 192, 224, 256] {
+                let mut entropy = vec![0u8; bits / 8];
+                rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut entropy);
+                let mnemonic = Mnemonic::from_entropy_in(*lang, &entropy).unwrap();
+                assert_eq!(mnemonic.word_count(), bits * 3 / 32);
+                assert_eq!(mnemonic.to_entropy(), entropy);
+                // the recomputed checksum matches the parsed phrase
+                Mnemonic::validate_in(*lang, mnemonic.as_str()).unwrap();
+                assert_eq!(
+                    Mnemonic::from_entropy_in(*lang, &mnemonic.to_entropy()).unwrap(),
+                    mnemonic
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_invalid_entropy() {
         //between 128 and 256 bits, but not divisible by 32
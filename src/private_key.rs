@@ -1,3 +1,4 @@
+use crate::amino::{std_sign_doc_bytes, AminoMsg};
 use crate::mnemonic::Mnemonic;
 use crate::msg::Msg;
 use crate::public_key::{CosmosPublicKey, PublicKey};
@@ -7,12 +8,14 @@ use crate::utils::hex_str_to_bytes;
 use crate::{coin::Fee, coin::Tip, Address};
 use crate::{error::*, utils::contains_non_hex_chars};
 use cosmos_sdk_proto::cosmos::crypto::secp256k1::PubKey as ProtoSecp256k1Pubkey;
+use cosmos_sdk_proto::cosmos::tx::signing::v1beta1::SignMode;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::Tx;
 use cosmos_sdk_proto::cosmos::tx::v1beta1::{
-    mode_info, AuthInfo, ModeInfo, SignDoc, SignerInfo, TxBody, TxRaw,
+    mode_info, AuthInfo, ModeInfo, SignDoc, SignDocDirectAux, SignerInfo, TxBody, TxRaw,
 };
 use num256::Uint256;
 use prost::Message;
+use prost_types::Any;
 use secp256k1::constants::CURVE_ORDER as CurveN;
 use secp256k1::Message as CurveMessage;
 use secp256k1::Scalar;
@@ -21,6 +24,7 @@ use secp256k1::{PublicKey as PublicKeyEC, SecretKey};
 use sha2::Sha512;
 use sha2::{Digest, Sha256};
 use std::cell::RefCell;
+use std::ops::Range;
 use std::str::FromStr;
 
 thread_local! {
@@ -67,19 +71,100 @@ pub trait PrivateKey: Clone + Sized {
 
     fn to_address(&self, prefix: &str) -> Result<Address, PrivateKeyError>;
 
+    /// Returns the public key for this private key packed into an Any using the
+    /// type url the chain expects for this key type, as used in a SignerInfo
+    fn to_public_key_any(&self) -> Result<Any, PrivateKeyError>;
+
+    /// Signs the provided bytes in the same way this key type signs a transaction
+    /// SignDoc, returning the raw signature bytes. Cosmos keys produce a compact secp256k1
+    /// signature over the sha256 of `bytes`, Ethermint keys sign the keccak256 of `bytes`.
+    ///
+    /// This can be used for off chain authentication such as ADR-036 `signArbitrary`,
+    /// where `bytes` is the serialized sign doc wrapping the message, without crafting
+    /// a dummy transaction. See CosmosPublicKey::verify_bytes to check the result
+    fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>, PrivateKeyError>;
+
+    /// Signs a transaction that contains at least one message using a single
+    /// private key, returns the standard Tx type, useful for simulations
     fn get_signed_tx(
         &self,
         messages: &[Msg],
         args: MessageArgs,
         memo: &str,
-    ) -> Result<Tx, PrivateKeyError>;
+    ) -> Result<Tx, PrivateKeyError> {
+        let parts = build_tx(self, messages, args, memo)?;
+        Ok(Tx {
+            body: Some(parts.body),
+            auth_info: Some(parts.auth_info),
+            signatures: parts.signatures,
+        })
+    }
 
+    /// Signs a transaction that contains at least one message using a single
+    /// private key. Signing is deterministic, secp256k1 signatures use RFC6979 nonces and
+    /// the protobuf encoding of the transaction is canonical, so signing the same messages,
+    /// args, and memo with the same key always produces byte for byte identical output
     fn sign_std_msg(
         &self,
         messages: &[Msg],
         args: MessageArgs,
         memo: &str,
-    ) -> Result<Vec<u8>, PrivateKeyError>;
+    ) -> Result<Vec<u8>, PrivateKeyError> {
+        let parts = build_tx(self, messages, args, memo)?;
+
+        let tx_raw = TxRaw {
+            body_bytes: parts.body_buf,
+            auth_info_bytes: parts.auth_buf,
+            signatures: parts.signatures,
+        };
+
+        let mut txraw_buf = Vec::new();
+        tx_raw.encode(&mut txraw_buf)?;
+        let digest = Sha256::digest(&txraw_buf);
+        trace!("TXID {}", bytes_to_hex_str(&digest));
+
+        Ok(txraw_buf)
+    }
+
+    /// Signs a transaction using SIGN_MODE_LEGACY_AMINO_JSON rather than SIGN_MODE_DIRECT,
+    /// as required by hardware wallets such as the Ledger. `amino_messages` must hold the
+    /// amino JSON form of each entry in `messages`, in the same order, since the chain
+    /// rebuilds the amino sign doc from the protobuf messages to verify the signature.
+    /// Tips can not be signed in this mode. Returns the serialized TxRaw like sign_std_msg
+    fn sign_std_msg_amino(
+        &self,
+        messages: &[Msg],
+        amino_messages: &[AminoMsg],
+        args: MessageArgs,
+        memo: &str,
+    ) -> Result<Vec<u8>, PrivateKeyError> {
+        if messages.len() != amino_messages.len() {
+            return Err(PrivateKeyError::InvalidAminoMsgs(format!(
+                "{} messages but {} amino messages",
+                messages.len(),
+                amino_messages.len()
+            )));
+        }
+        if args.tip.is_some() {
+            return Err(PrivateKeyError::InvalidAminoMsgs(
+                "tips are not supported in amino json sign mode".to_string(),
+            ));
+        }
+        let signer_info = build_signer_info(
+            self.to_public_key_any()?,
+            args.sequence,
+            SignMode::LegacyAminoJson,
+        );
+        let unfinished = build_unfinished_tx(vec![signer_info], messages, args.clone(), memo)?;
+        let signature = self.sign_bytes(&std_sign_doc_bytes(amino_messages, &args, memo))?;
+
+        let tx_raw = TxRaw {
+            body_bytes: unfinished.body_buf,
+            auth_info_bytes: unfinished.auth_buf,
+            signatures: vec![signature],
+        };
+        Ok(tx_raw.encode_to_vec())
+    }
 }
 
 /// This structure represents a private key of a Cosmos Network.
@@ -132,48 +217,40 @@ impl PrivateKey for CosmosPrivateKey {
         Ok(address)
     }
 
-    /// Signs a transaction that contains at least one message using a single
-    /// private key, returns the standard Tx type, useful for simulations
-    fn get_signed_tx(
-        &self,
-        messages: &[Msg],
-        args: MessageArgs,
-        memo: &str,
-    ) -> Result<Tx, PrivateKeyError> {
-        let parts = self.build_tx(messages, args, memo)?;
-        Ok(Tx {
-            body: Some(parts.body),
-            auth_info: Some(parts.auth_info),
-            signatures: parts.signatures,
-        })
-    }
-
-    /// Signs a transaction that contains at least one message using a single
-    /// private key.
-    fn sign_std_msg(
-        &self,
-        messages: &[Msg],
-        args: MessageArgs,
-        memo: &str,
-    ) -> Result<Vec<u8>, PrivateKeyError> {
-        let parts = self.build_tx(messages, args, memo)?;
-
-        let tx_raw = TxRaw {
-            body_bytes: parts.body_buf,
-            auth_info_bytes: parts.auth_buf,
-            signatures: parts.signatures,
+    fn to_public_key_any(&self) -> Result<Any, PrivateKeyError> {
+        // prefix does not matter in this case, you could use a blank string
+        let our_pubkey = self.to_public_key(CosmosPublicKey::DEFAULT_PREFIX)?;
+        let key = ProtoSecp256k1Pubkey {
+            key: our_pubkey.to_vec(),
         };
+        Ok(encode_any(key, "/cosmos.crypto.secp256k1.PubKey"))
+    }
 
-        let mut txraw_buf = Vec::new();
-        tx_raw.encode(&mut txraw_buf).unwrap();
-        let digest = Sha256::digest(&txraw_buf);
-        trace!("TXID {}", bytes_to_hex_str(&digest));
-
-        Ok(txraw_buf)
+    /// Signs the sha256 digest of the provided bytes, producing a compact secp256k1 signature
+    fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>, PrivateKeyError> {
+        let secp256k1 = Secp256k1::new();
+        let sk = SecretKey::from_slice(&self.0)?;
+        let digest = Sha256::digest(bytes);
+        let msg = CurveMessage::from_digest_slice(&digest)?;
+        let signed = secp256k1.sign_ecdsa(&msg, &sk);
+        Ok(signed.serialize_compact().to_vec())
     }
 }
 
 impl CosmosPrivateKey {
+    /// Derives a range of keys from a mnemonic phrase, replacing the final component of
+    /// `base_path` with each of the `account_indices`. Useful for scanning m/44'/118'/0'/0/{0..n}
+    /// when recovering a wallet that may have used more than the first account
+    pub fn derive_range(
+        phrase: &str,
+        passphrase: &str,
+        base_path: &str,
+        account_indices: Range<u32>,
+    ) -> Result<Vec<CosmosPrivateKey>, PrivateKeyError> {
+        let keys = derive_hd_range(base_path, phrase, passphrase, account_indices)?;
+        Ok(keys.into_iter().map(CosmosPrivateKey).collect())
+    }
+
     /// Obtain a public key for a given private key
     pub fn to_public_key(&self, prefix: &str) -> Result<CosmosPublicKey, PrivateKeyError> {
         let secp256k1 = Secp256k1::new();
@@ -182,54 +259,6 @@ impl CosmosPrivateKey {
         let compressed = pkey.serialize();
         Ok(CosmosPublicKey::from_bytes(compressed, prefix)?)
     }
-
-    /// Internal function that that handles building a single message to sign
-    /// returns an internal struct containing the parts of the built transaction
-    /// in a way that's easy to mix and match for various uses and output types.
-    fn build_tx(
-        &self,
-        messages: &[Msg],
-        args: MessageArgs,
-        memo: impl Into<String>,
-    ) -> Result<TxParts, PrivateKeyError> {
-        // prefix does not matter in this case, you could use a blank string
-        let our_pubkey = self.to_public_key(CosmosPublicKey::DEFAULT_PREFIX)?;
-
-        let key = ProtoSecp256k1Pubkey {
-            key: our_pubkey.to_vec(),
-        };
-
-        let mut unfinished = build_unfinished_tx(
-            key,
-            "/cosmos.crypto.secp256k1.PubKey",
-            messages,
-            args.clone(),
-            memo,
-        );
-
-        let sign_doc = SignDoc {
-            body_bytes: unfinished.body_buf.clone(),
-            auth_info_bytes: unfinished.auth_buf.clone(),
-            chain_id: args.chain_id.to_string(),
-            account_number: args.account_number,
-        };
-
-        // Protobuf serialization of `SignDoc`
-        let mut signdoc_buf = Vec::new();
-        sign_doc.encode(&mut signdoc_buf).unwrap();
-
-        let secp256k1 = Secp256k1::new();
-        let sk = SecretKey::from_slice(&self.0)?;
-        let digest = Sha256::digest(&signdoc_buf);
-        let msg = CurveMessage::from_digest_slice(&digest)?;
-        // Sign the signdoc
-        let signed = secp256k1.sign_ecdsa(&msg, &sk);
-        let compact = signed.serialize_compact().to_vec();
-
-        // Finish the TxParts and return
-        unfinished.signatures = vec![compact];
-        Ok(unfinished)
-    }
 }
 
 impl FromStr for CosmosPrivateKey {
@@ -305,45 +334,46 @@ impl PrivateKey for EthermintPrivateKey {
         Ok(address)
     }
 
-    fn get_signed_tx(
-        &self,
-        messages: &[Msg],
-        args: MessageArgs,
-        memo: &str,
-    ) -> Result<Tx, PrivateKeyError> {
-        let parts = self.build_tx(messages, args, memo)?;
-        Ok(Tx {
-            body: Some(parts.body),
-            auth_info: Some(parts.auth_info),
-            signatures: parts.signatures,
-        })
-    }
-
-    fn sign_std_msg(
-        &self,
-        messages: &[Msg],
-        args: MessageArgs,
-        memo: &str,
-    ) -> Result<Vec<u8>, PrivateKeyError> {
-        let parts = self.build_tx(messages, args, memo)?;
-
-        let tx_raw = TxRaw {
-            body_bytes: parts.body_buf,
-            auth_info_bytes: parts.auth_buf,
-            signatures: parts.signatures,
+    fn to_public_key_any(&self) -> Result<Any, PrivateKeyError> {
+        let our_pubkey = self.to_public_key(CosmosPublicKey::DEFAULT_PREFIX)?;
+        // TODO: Use the ethermint proto here, not the cosmos-sdk one
+        let pubkey_proto = ProtoSecp256k1Pubkey {
+            key: our_pubkey.to_vec(),
         };
+        Ok(encode_any(
+            pubkey_proto,
+            "/ethermint.crypto.v1.ethsecp256k1.PubKey",
+        ))
+    }
 
-        let mut txraw_buf = Vec::new();
-        tx_raw.encode(&mut txraw_buf).unwrap();
-        let digest = Sha256::digest(&txraw_buf);
-        trace!("TXID {}", bytes_to_hex_str(&digest));
-
-        Ok(txraw_buf)
+    /// Signs the keccak256 digest of the provided bytes, producing a 65 byte recoverable signature
+    /// with a low S value
+    fn sign_bytes(&self, bytes: &[u8]) -> Result<Vec<u8>, PrivateKeyError> {
+        // clarity does not validate the key, so check it is a valid secp256k1 key first
+        SecretKey::from_slice(&self.0)?;
+        let clarity_sk = clarity::PrivateKey::from_bytes(self.0)
+            .map_err(|e| PrivateKeyError::InvalidSecretKey(format!("{e:?}")))?;
+        let signed = clarity_sk.sign_insecure_msg(bytes);
+        let mut signature = signed.to_bytes().to_vec();
+        normalize_recoverable_s(&mut signature);
+        Ok(signature)
     }
 }
 
 #[cfg(feature = "ethermint")]
 impl EthermintPrivateKey {
+    /// Derives a range of keys from a mnemonic phrase, replacing the final component of
+    /// `base_path` with each of the `account_indices`. See CosmosPrivateKey::derive_range
+    pub fn derive_range(
+        phrase: &str,
+        passphrase: &str,
+        base_path: &str,
+        account_indices: Range<u32>,
+    ) -> Result<Vec<EthermintPrivateKey>, PrivateKeyError> {
+        let keys = derive_hd_range(base_path, phrase, passphrase, account_indices)?;
+        Ok(keys.into_iter().map(EthermintPrivateKey).collect())
+    }
+
     fn to_public_key(
         self,
         prefix: &str,
@@ -361,51 +391,6 @@ impl EthermintPrivateKey {
         let pubkey = crate::public_key::EthermintPublicKey::from_bytes(pkey, prefix)?;
         Ok(pubkey)
     }
-
-    /// Internal function that that handles building a single message to sign
-    /// returns an internal struct containing the parts of the built transaction
-    /// in a way that's easy to mix and match for various uses and output types.
-    fn build_tx(
-        &self,
-        messages: &[Msg],
-        args: MessageArgs,
-        memo: impl Into<String>,
-    ) -> Result<TxParts, PrivateKeyError> {
-        let our_pubkey = self.to_public_key(CosmosPublicKey::DEFAULT_PREFIX)?;
-
-        // TODO: Use the ethermint proto here, not the cosmos-sdk one
-        let pubkey_proto = ProtoSecp256k1Pubkey {
-            key: our_pubkey.to_vec(),
-        };
-
-        let mut unfinished = build_unfinished_tx(
-            pubkey_proto,
-            "/ethermint.crypto.v1.ethsecp256k1.PubKey",
-            messages,
-            args.clone(),
-            memo,
-        );
-
-        let sign_doc = SignDoc {
-            body_bytes: unfinished.body_buf.clone(),
-            auth_info_bytes: unfinished.auth_buf.clone(),
-            chain_id: args.chain_id.to_string(),
-            account_number: args.account_number,
-        };
-
-        // Protobuf serialization of `SignDoc`
-        let mut signdoc_buf = Vec::new();
-        sign_doc.encode(&mut signdoc_buf).unwrap();
-
-        // Sign the signdoc
-        let clarity_sk = clarity::PrivateKey::from_bytes(self.0).unwrap();
-
-        let signed = clarity_sk.sign_insecure_msg(&signdoc_buf);
-
-        // Finish the TxParts and return
-        unfinished.signatures = vec![signed.to_bytes().to_vec()];
-        Ok(unfinished)
-    }
 }
 
 #[cfg(feature = "ethermint")]
@@ -464,6 +449,30 @@ fn from_secret(secret: &[u8]) -> [u8; 32] {
     result
 }
 
+/// Cosmos chains reject signatures with an S value in the upper half of the curve order, this
+/// negates S of an r|s|v recoverable signature if required and flips the recovery id to match
+#[cfg(feature = "ethermint")]
+fn normalize_recoverable_s(signature: &mut [u8]) {
+    let n = Uint256::from_be_bytes(&CurveN);
+    let half_n = n / 2u8.into();
+    let s = Uint256::from_be_bytes(&signature[32..64]);
+    if s > half_n {
+        let low_s = n - s;
+        signature[32..64].copy_from_slice(&low_s.to_be_bytes());
+        signature[64] = flip_recovery_id(signature[64]);
+    }
+}
+
+/// Flips the parity of a recovery id, which may be in either the 0/1 or 27/28 form
+#[cfg(feature = "ethermint")]
+fn flip_recovery_id(v: u8) -> u8 {
+    match v {
+        27 => 28,
+        28 => 27,
+        v => v ^ 1,
+    }
+}
+
 /// Derives a private key from a mnemonic phrase and passphrase, using a BIP-44 HDPath
 /// The actual seed bytes are derived from the mnemonic phrase, which are then used to derive
 /// the root of a Bip32 HD wallet. From that application private keys are derived
@@ -476,6 +485,45 @@ fn from_hd_wallet_path(
     phrase: &str,
     passphrase: &str,
 ) -> Result<[u8; 32], PrivateKeyError> {
+    let path = parse_hd_path(hd_path)?;
+    let (master_secret_key, master_chain_code) = master_key_from_phrase(phrase, passphrase)?;
+    let (secret_key, _) = derive_path(master_secret_key, master_chain_code, &path)?;
+    Ok(secret_key)
+}
+
+/// Derives a private key for each of the `account_indices`, substituting each index for the last
+/// component of `base_path`, e.g. m/44'/118'/0'/0/0 with indices 0..3 derives m/44'/118'/0'/0/0,
+/// m/44'/118'/0'/0/1 and m/44'/118'/0'/0/2. The seed and the parent of the last component are
+/// derived only once, so this is much faster than calling from_hd_wallet_path for each index
+/// Note: This implementation is shared between Ethereum and standard Cosmos-SDK chains
+fn derive_hd_range(
+    base_path: &str,
+    phrase: &str,
+    passphrase: &str,
+    account_indices: Range<u32>,
+) -> Result<Vec<[u8; 32]>, PrivateKeyError> {
+    let path = parse_hd_path(base_path)?;
+    let (_, hardened) = match path.last() {
+        Some(last) => *last,
+        None => return Err(HdWalletError::InvalidPathSpec(base_path.to_string()).into()),
+    };
+    let (master_secret_key, master_chain_code) = master_key_from_phrase(phrase, passphrase)?;
+    let (parent_key, parent_chain_code) = derive_path(
+        master_secret_key,
+        master_chain_code,
+        &path[..path.len() - 1],
+    )?;
+
+    let mut keys = Vec::new();
+    for index in account_indices {
+        let (secret_key, _) = get_child_key(parent_key, parent_chain_code, index, hardened)?;
+        keys.push(secret_key);
+    }
+    Ok(keys)
+}
+
+/// Parses a BIP-44 HDPath such as m/44'/118'/0'/0/0 into a list of (index, hardened) components
+fn parse_hd_path(hd_path: &str) -> Result<Vec<(u32, bool)>, PrivateKeyError> {
     if !hd_path.starts_with('m') || hd_path.contains('\\') {
         return Err(HdWalletError::InvalidPathSpec(hd_path.to_string()).into());
     }
@@ -483,12 +531,7 @@ fn from_hd_wallet_path(
     // discard the m
     let _ = iterator.next();
 
-    let key_import = Mnemonic::from_str(phrase)?;
-    let seed_bytes = key_import.to_seed(passphrase);
-    let (master_secret_key, master_chain_code) = master_key_from_seed(&seed_bytes);
-    let mut secret_key = master_secret_key;
-    let mut chain_code = master_chain_code;
-
+    let mut path = Vec::new();
     for mut val in iterator {
         let mut hardened = false;
         if val.contains('\'') {
@@ -496,19 +539,41 @@ fn from_hd_wallet_path(
             val = val.trim_matches('\'');
         }
         if let Ok(parsed_int) = val.parse() {
-            let (s, c) = get_child_key(secret_key, chain_code, parsed_int, hardened);
-            secret_key = s;
-            chain_code = c;
+            path.push((parsed_int, hardened));
         } else {
             return Err(HdWalletError::InvalidPathSpec(hd_path.to_string()).into());
         }
     }
-    Ok(secret_key)
+    Ok(path)
+}
+
+/// Derives the master key and chain code of a Bip32 HD wallet from a mnemonic phrase and passphrase
+fn master_key_from_phrase(
+    phrase: &str,
+    passphrase: &str,
+) -> Result<([u8; 32], [u8; 32]), PrivateKeyError> {
+    let key_import = Mnemonic::from_str(phrase)?;
+    let seed_bytes = key_import.to_seed(passphrase);
+    master_key_from_seed(&seed_bytes)
+}
+
+/// Follows the parsed `path` from the given key and chain code, returning the final key and chain code
+fn derive_path(
+    mut secret_key: [u8; 32],
+    mut chain_code: [u8; 32],
+    path: &[(u32, bool)],
+) -> Result<([u8; 32], [u8; 32]), PrivateKeyError> {
+    for (index, hardened) in path {
+        let (s, c) = get_child_key(secret_key, chain_code, *index, *hardened)?;
+        secret_key = s;
+        chain_code = c;
+    }
+    Ok((secret_key, chain_code))
 }
 
 /// This derives the master key from seed bytes, the actual usage is typically
 /// for Cosmos key_import support, where we import a seed phrase.
-fn master_key_from_seed(seed_bytes: &[u8]) -> ([u8; 32], [u8; 32]) {
+fn master_key_from_seed(seed_bytes: &[u8]) -> Result<([u8; 32], [u8; 32]), PrivateKeyError> {
     use hmac::Hmac;
     use hmac::Mac;
     type HmacSha512 = Hmac<Sha512>;
@@ -522,9 +587,9 @@ fn master_key_from_seed(seed_bytes: &[u8]) -> ([u8; 32], [u8; 32]) {
     master_chain_code.copy_from_slice(&hash[32..64]);
 
     // key check
-    let _ = SecretKey::from_slice(&master_secret_key).unwrap();
+    SecretKey::from_slice(&master_secret_key)?;
 
-    (master_secret_key, master_chain_code)
+    Ok((master_secret_key, master_chain_code))
 }
 
 /// This keys the child key following the bip32 https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki
@@ -535,7 +600,7 @@ fn get_child_key(
     c_parent: [u8; 32],
     i: u32,
     hardened: bool,
-) -> ([u8; 32], [u8; 32]) {
+) -> Result<([u8; 32], [u8; 32]), PrivateKeyError> {
     use hmac::Hmac;
     use hmac::Mac;
     type HmacSha512 = Hmac<Sha512>;
@@ -547,7 +612,7 @@ fn get_child_key(
         hasher.update(&k_parent);
     } else {
         let scep = Secp256k1::new();
-        let private_key = SecretKey::from_slice(&k_parent).unwrap();
+        let private_key = SecretKey::from_slice(&k_parent)?;
         let public_key = PublicKeyEC::from_secret_key(&scep, &private_key);
         hasher.update(&public_key.serialize());
     }
@@ -574,27 +639,162 @@ fn get_child_key(
     //     panic!("child key not in curve space!")
     // }
 
-    let k_parent = Scalar::from_be_bytes(k_parent).unwrap();
+    let k_parent = Scalar::from(SecretKey::from_slice(&k_parent)?);
 
-    let mut parse_i_l = SecretKey::from_slice(&l_param[0..32]).unwrap();
-    parse_i_l = parse_i_l.add_tweak(&k_parent).unwrap();
-    let child_key = parse_i_l;
+    let parse_i_l = SecretKey::from_slice(&l_param[0..32])?;
+    let child_key = parse_i_l.add_tweak(&k_parent)?;
 
-    let mut child_key_res: [u8; 32] = [0; 32];
-    child_key_res
-        .copy_from_slice(&hex_str_to_bytes(&child_key.display_secret().to_string()).unwrap());
+    let child_key_res: [u8; 32] = child_key.secret_bytes();
     let mut chain_code_res: [u8; 32] = [0; 32];
     chain_code_res.copy_from_slice(&l_param[32..64]);
-    (child_key_res, chain_code_res)
+    Ok((child_key_res, chain_code_res))
+}
+
+/// Signs a transaction with multiple private keys, each SignerInfo in the resulting
+/// AuthInfo and each signature in the returned TxRaw are in the same order as `signers`.
+/// Every signer provides its own MessageArgs so that the correct sequence and account
+/// number are used for each signature, the fee, tip, and timeout height are taken from the
+/// first signer's MessageArgs and shared by the whole transaction
+pub fn sign_multi<K: PrivateKey>(
+    messages: &[Msg],
+    signers: &[(K, MessageArgs)],
+    memo: &str,
+) -> Result<TxRaw, PrivateKeyError> {
+    let (_, shared_args) = signers.first().ok_or(PrivateKeyError::NoSigners)?;
+
+    let mut signer_infos = Vec::new();
+    for (key, args) in signers {
+        signer_infos.push(build_signer_info(
+            key.to_public_key_any()?,
+            args.sequence,
+            SignMode::Direct,
+        ));
+    }
+    let mut unfinished = build_unfinished_tx(signer_infos, messages, shared_args.clone(), memo)?;
+
+    for (key, args) in signers {
+        let signdoc_buf = build_sign_doc(&unfinished, args)?;
+        unfinished.signatures.push(key.sign_bytes(&signdoc_buf)?);
+    }
+
+    Ok(TxRaw {
+        body_bytes: unfinished.body_buf,
+        auth_info_bytes: unfinished.auth_buf,
+        signatures: unfinished.signatures,
+    })
+}
+
+/// Signs a tipped transaction, where `tipper` is the signer of the messages and pays `tip`
+/// while `fee_payer` pays the transaction fee, as used for paying fees in a non native token on
+/// chains that support it. The tipper signs with SIGN_MODE_DIRECT_AUX, so it does not commit to
+/// the fee, and the fee payer signs the complete transaction with SIGN_MODE_DIRECT as the last
+/// signer. The fee, timeout height, and chain-id are taken from `fee_payer_args`, the fee payer
+/// field of the fee should be set to the address of the fee payer
+pub fn sign_tipped<T: PrivateKey, F: PrivateKey>(
+    messages: &[Msg],
+    memo: &str,
+    tip: Tip,
+    tipper: &T,
+    tipper_args: &MessageArgs,
+    fee_payer: &F,
+    fee_payer_args: &MessageArgs,
+) -> Result<TxRaw, PrivateKeyError> {
+    let tipper_public_key = tipper.to_public_key_any()?;
+    let signer_infos = vec![
+        build_signer_info(
+            tipper_public_key.clone(),
+            tipper_args.sequence,
+            SignMode::DirectAux,
+        ),
+        build_signer_info(
+            fee_payer.to_public_key_any()?,
+            fee_payer_args.sequence,
+            SignMode::Direct,
+        ),
+    ];
+    let mut args = fee_payer_args.clone();
+    args.tip = Some(tip.clone());
+    let mut unfinished = build_unfinished_tx(signer_infos, messages, args.clone(), memo)?;
+
+    let aux_sign_doc = SignDocDirectAux {
+        body_bytes: unfinished.body_buf.clone(),
+        public_key: Some(tipper_public_key),
+        chain_id: args.chain_id.clone(),
+        account_number: tipper_args.account_number,
+        sequence: tipper_args.sequence,
+        tip: Some(tip.into()),
+    };
+    unfinished
+        .signatures
+        .push(tipper.sign_bytes(&aux_sign_doc.encode_to_vec())?);
+
+    let signdoc_buf = build_sign_doc(&unfinished, &args)?;
+    unfinished
+        .signatures
+        .push(fee_payer.sign_bytes(&signdoc_buf)?);
+
+    Ok(TxRaw {
+        body_bytes: unfinished.body_buf,
+        auth_info_bytes: unfinished.auth_buf,
+        signatures: unfinished.signatures,
+    })
+}
+
+/// Internal function that that handles building a single message to sign
+/// returns an internal struct containing the parts of the built transaction
+/// in a way that's easy to mix and match for various uses and output types.
+fn build_tx<K: PrivateKey>(
+    key: &K,
+    messages: &[Msg],
+    args: MessageArgs,
+    memo: impl Into<String>,
+) -> Result<TxParts, PrivateKeyError> {
+    let signer_info = build_signer_info(key.to_public_key_any()?, args.sequence, SignMode::Direct);
+    let mut unfinished = build_unfinished_tx(vec![signer_info], messages, args.clone(), memo)?;
+
+    // Sign the signdoc
+    let signdoc_buf = build_sign_doc(&unfinished, &args)?;
+    let signature = key.sign_bytes(&signdoc_buf)?;
+
+    // Finish the TxParts and return
+    unfinished.signatures = vec![signature];
+    Ok(unfinished)
+}
+
+/// Produces the protobuf serialization of the `SignDoc` for a given signer
+fn build_sign_doc(unfinished: &TxParts, args: &MessageArgs) -> Result<Vec<u8>, PrivateKeyError> {
+    let sign_doc = SignDoc {
+        body_bytes: unfinished.body_buf.clone(),
+        auth_info_bytes: unfinished.auth_buf.clone(),
+        chain_id: args.chain_id.to_string(),
+        account_number: args.account_number,
+    };
+
+    let mut signdoc_buf = Vec::new();
+    sign_doc.encode(&mut signdoc_buf)?;
+    Ok(signdoc_buf)
+}
+
+fn build_signer_info(public_key: Any, sequence: u64, mode: SignMode) -> SignerInfo {
+    let single = mode_info::Single { mode: mode.into() };
+
+    let mode = Some(ModeInfo {
+        sum: Some(mode_info::Sum::Single(single)),
+    });
+
+    SignerInfo {
+        public_key: Some(public_key),
+        mode_info: mode,
+        sequence,
+    }
 }
 
-fn build_unfinished_tx<P: prost::Message>(
-    pubkey_proto: P,
-    proto_type_url: &str,
+fn build_unfinished_tx(
+    signer_infos: Vec<SignerInfo>,
     messages: &[Msg],
     args: MessageArgs,
     memo: impl Into<String>,
-) -> TxParts {
+) -> Result<TxParts, PrivateKeyError> {
     // Create TxBody
     let body = TxBody {
         messages: messages.iter().map(|msg| msg.0.clone()).collect(),
@@ -606,38 +806,42 @@ fn build_unfinished_tx<P: prost::Message>(
 
     // A protobuf serialization of a TxBody
     let mut body_buf = Vec::new();
-    body.encode(&mut body_buf).unwrap();
-
-    let pk_any = encode_any(pubkey_proto, proto_type_url.to_string());
-
-    let single = mode_info::Single { mode: 1 };
-
-    let mode = Some(ModeInfo {
-        sum: Some(mode_info::Sum::Single(single)),
-    });
-
-    let signer_info = SignerInfo {
-        public_key: Some(pk_any),
-        mode_info: mode,
-        sequence: args.sequence,
-    };
+    body.encode(&mut body_buf)?;
 
     let auth_info = AuthInfo {
-        signer_infos: vec![signer_info],
+        signer_infos,
         fee: Some(args.fee.into()),
         tip: args.tip.map(|v| v.into()),
     };
 
     // Protobuf serialization of `AuthInfo`
     let mut auth_buf = Vec::new();
-    auth_info.encode(&mut auth_buf).unwrap();
+    auth_info.encode(&mut auth_buf)?;
 
-    TxParts {
+    Ok(TxParts {
         body,
         body_buf,
         auth_info,
         auth_buf,
         signatures: vec![], // Unfinished
+    })
+}
+
+/// Default `MessageArgs` shared by tests, override fields with struct update syntax
+#[cfg(test)]
+pub(crate) fn test_message_args() -> MessageArgs {
+    MessageArgs {
+        sequence: 0,
+        fee: Fee {
+            amount: vec![],
+            gas_limit: 200000,
+            payer: None,
+            granter: None,
+        },
+        tip: None,
+        timeout_height: 100,
+        chain_id: "chain-0".to_string(),
+        account_number: 1,
     }
 }
 
@@ -679,13 +883,14 @@ fn test_cosmos_key_derivation_manual() {
     // first test the underlying functions manually
     let key_import = Mnemonic::from_str(words).unwrap();
     let seed_bytes = key_import.to_seed("");
-    let (master_secret_key, master_chain_code) = master_key_from_seed(&seed_bytes);
-    let (m44h, c44h) = get_child_key(master_secret_key, master_chain_code, 44, true);
-    let (m44h_118h, c44h_118h) = get_child_key(m44h, c44h, 118, true);
-    let (m44h_118h_0h, c44h_118h_0h) = get_child_key(m44h_118h, c44h_118h, 0, true);
-    let (m44h_118h_0h_0, c44h_118h_0h_0) = get_child_key(m44h_118h_0h, c44h_118h_0h, 0, false);
+    let (master_secret_key, master_chain_code) = master_key_from_seed(&seed_bytes).unwrap();
+    let (m44h, c44h) = get_child_key(master_secret_key, master_chain_code, 44, true).unwrap();
+    let (m44h_118h, c44h_118h) = get_child_key(m44h, c44h, 118, true).unwrap();
+    let (m44h_118h_0h, c44h_118h_0h) = get_child_key(m44h_118h, c44h_118h, 0, true).unwrap();
+    let (m44h_118h_0h_0, c44h_118h_0h_0) =
+        get_child_key(m44h_118h_0h, c44h_118h_0h, 0, false).unwrap();
     let (m44h_118h_0h_0_0, _c44h_118h_0h_0_0) =
-        get_child_key(m44h_118h_0h_0, c44h_118h_0h_0, 0, false);
+        get_child_key(m44h_118h_0h_0, c44h_118h_0h_0, 0, false).unwrap();
 
     let private_key = CosmosPrivateKey(m44h_118h_0h_0_0);
     let public_key = private_key.to_public_key("cosmospub").unwrap();
@@ -717,11 +922,34 @@ fn test_cosmos_key_derivation_with_path_parsing() {
     );
 }
 
+#[test]
+fn test_derive_range() {
+    let words = "purse sure leg gap above pull rescue glass circle attract erupt can sail gasp shy clarify inflict anger sketch hobby scare mad reject where";
+    let keys = CosmosPrivateKey::derive_range(words, "", "m/44'/118'/0'/0/0", 0..4).unwrap();
+    assert_eq!(keys.len(), 4);
+    assert_eq!(keys[0], CosmosPrivateKey::from_phrase(words, "").unwrap());
+    for (i, key) in keys.iter().enumerate() {
+        let path = format!("m/44'/118'/0'/0/{i}");
+        assert_eq!(
+            *key,
+            CosmosPrivateKey::from_hd_wallet_path(&path, words, "").unwrap()
+        );
+    }
+    // hardened final components are kept hardened
+    let keys = CosmosPrivateKey::derive_range(words, "", "m/44'/118'/0'", 2..3).unwrap();
+    assert_eq!(
+        keys[0],
+        CosmosPrivateKey::from_hd_wallet_path("m/44'/118'/2'", words, "").unwrap()
+    );
+    assert!(CosmosPrivateKey::derive_range(words, "", "m", 0..1).is_err());
+    assert!(CosmosPrivateKey::derive_range(words, "", "44'/118'", 0..1).is_err());
+}
+
 #[test]
 /// This tests deriving HD wallet keys from a given seed and i value
 fn test_vector_hardened() {
     let seed = hex_str_to_bytes("000102030405060708090a0b0c0d0e0f").unwrap();
-    let (master_privkey, master_chaincode) = master_key_from_seed(&seed);
+    let (master_privkey, master_chaincode) = master_key_from_seed(&seed).unwrap();
     let correct_master_privkey =
         hex_str_to_bytes("e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35")
             .unwrap();
@@ -737,7 +965,7 @@ fn test_vector_hardened() {
     // now we try deriving some child keys
 
     // hardened zero
-    let (m0_dash, c0_dash) = get_child_key(master_privkey, master_chaincode, 0, true);
+    let (m0_dash, c0_dash) = get_child_key(master_privkey, master_chaincode, 0, true).unwrap();
     let correct_m0_dash_chaincode =
         hex_str_to_bytes("47fdacbd0f1097043b78c63c20c34ef4ed9a111d980047ad16282c7ae6236141")
             .unwrap();
@@ -755,7 +983,7 @@ fn test_vector_hardened() {
 fn test_vector_unhardened() {
     // new seed for unhardened test
     let seed = hex_str_to_bytes("fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542").unwrap();
-    let (master_privkey, master_chaincode) = master_key_from_seed(&seed);
+    let (master_privkey, master_chaincode) = master_key_from_seed(&seed).unwrap();
     let correct_master_privkey =
         hex_str_to_bytes("4b03d6fc340455b363f51020ad3ecca4f0850280cf436c70c727923f6db46c3e")
             .unwrap();
@@ -769,7 +997,7 @@ fn test_vector_unhardened() {
     assert_eq!(master_chaincode.to_vec(), correct_master_chaincode);
 
     //m/0
-    let (m0, c0) = get_child_key(master_privkey, master_chaincode, 0, false);
+    let (m0, c0) = get_child_key(master_privkey, master_chaincode, 0, false).unwrap();
     let correct_m0_chaincode =
         hex_str_to_bytes("f0909affaa7ee7abe5dd4e100598d4dc53cd709d5a5c2cac40e7412f232f7c9c")
             .unwrap();
@@ -782,7 +1010,7 @@ fn test_vector_unhardened() {
     assert_eq!(c0.to_vec(), correct_m0_chaincode);
 
     //m/0/2147483647'
-    let (m0, c0) = get_child_key(m0, c0, 2147483647, true);
+    let (m0, c0) = get_child_key(m0, c0, 2147483647, true).unwrap();
     let correct_m0_chaincode =
         hex_str_to_bytes("be17a268474a6bb9c61e1d720cf6215e2a88c5406c4aee7b38547f585c9a37d9")
             .unwrap();
@@ -795,7 +1023,7 @@ fn test_vector_unhardened() {
     assert_eq!(c0.to_vec(), correct_m0_chaincode);
 
     //m/0/2147483647'/1
-    let (m0, c0) = get_child_key(m0, c0, 1, false);
+    let (m0, c0) = get_child_key(m0, c0, 1, false).unwrap();
     let correct_m0_chaincode =
         hex_str_to_bytes("f366f48f1ea9f2d1d3fe958c95ca84ea18e4c4ddb9366c336c927eb246fb38cb")
             .unwrap();
@@ -808,7 +1036,7 @@ fn test_vector_unhardened() {
     assert_eq!(c0.to_vec(), correct_m0_chaincode);
 
     //m/0/2147483647'/1/2147483646'
-    let (m0, c0) = get_child_key(m0, c0, 2147483646, true);
+    let (m0, c0) = get_child_key(m0, c0, 2147483646, true).unwrap();
     let correct_m0_chaincode =
         hex_str_to_bytes("637807030d55d01f9a0cb3a7839515d796bd07706386a6eddf06cc29a65a0e29")
             .unwrap();
@@ -936,17 +1164,9 @@ fn test_ethermint_signatures() {
         }],
     };
     let msg_args = MessageArgs {
-        sequence: 0,
-        fee: Fee {
-            amount: vec![],
-            gas_limit: 200000,
-            payer: None,
-            granter: None,
-        },
-        tip: None,
         timeout_height: 0,
-        chain_id: "chain-0".to_string(),
         account_number: 0,
+        ..test_message_args()
     };
     let msg = Msg(encode_any(msg_send, "/cosmos.bank.v1beta1.MsgSend"));
 
@@ -981,3 +1201,328 @@ fn test_bank_send_msg() {
         println!("output is {output:?}")
     });
 }
+
+#[test]
+fn test_sign_multi() {
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use secp256k1::ecdsa::Signature as EcdsaSignature;
+
+    let first = CosmosPrivateKey::from_secret(b"first");
+    let second = CosmosPrivateKey::from_secret(b"second");
+    let msg_send = MsgSend {
+        from_address: first.to_address("cosmos").unwrap().to_string(),
+        to_address: second.to_address("cosmos").unwrap().to_string(),
+        amount: vec![],
+    };
+    let msg = Msg::new("/cosmos.bank.v1beta1.MsgSend", msg_send);
+    let args = |sequence, account_number| MessageArgs {
+        sequence,
+        account_number,
+        ..test_message_args()
+    };
+
+    assert!(sign_multi::<CosmosPrivateKey>(&[msg.clone()], &[], "").is_err());
+
+    let tx = sign_multi(&[msg], &[(first, args(1, 5)), (second, args(7, 9))], "").unwrap();
+    assert_eq!(tx.signatures.len(), 2);
+    let auth_info = AuthInfo::decode(tx.auth_info_bytes.as_slice()).unwrap();
+    assert_eq!(auth_info.signer_infos.len(), 2);
+    assert_eq!(auth_info.signer_infos[0].sequence, 1);
+    assert_eq!(auth_info.signer_infos[1].sequence, 7);
+
+    let secp256k1 = Secp256k1::new();
+    for (i, (key, account_number)) in [(first, 5), (second, 9)].iter().enumerate() {
+        let sign_doc = SignDoc {
+            body_bytes: tx.body_bytes.clone(),
+            auth_info_bytes: tx.auth_info_bytes.clone(),
+            chain_id: "chain-0".to_string(),
+            account_number: *account_number,
+        };
+        let digest = Sha256::digest(sign_doc.encode_to_vec());
+        let msg = CurveMessage::from_digest_slice(&digest).unwrap();
+        let signature = EcdsaSignature::from_compact(&tx.signatures[i]).unwrap();
+        let sk = SecretKey::from_slice(&key.0).unwrap();
+        let pk = PublicKeyEC::from_secret_key(&secp256k1, &sk);
+        assert!(secp256k1.verify_ecdsa(&msg, &signature, &pk).is_ok());
+    }
+}
+
+#[test]
+fn test_verify_tx_signature() {
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    let key = CosmosPrivateKey::from_secret(b"mySecret");
+    let public_key = key.to_public_key("cosmospub").unwrap();
+    let msg_send = MsgSend {
+        from_address: key.to_address("cosmos").unwrap().to_string(),
+        to_address: key.to_address("cosmos").unwrap().to_string(),
+        amount: vec![],
+    };
+    let msg = Msg::new("/cosmos.bank.v1beta1.MsgSend", msg_send);
+    let args = MessageArgs {
+        sequence: 3,
+        account_number: 12,
+        ..test_message_args()
+    };
+
+    let parts = build_tx(&key, &[msg], args.clone(), "").unwrap();
+    let sign_doc = build_sign_doc(&parts, &args).unwrap();
+    assert!(public_key
+        .verify_tx_signature(&sign_doc, &parts.signatures[0])
+        .unwrap());
+
+    // a signature for another account number is not valid for this sign doc
+    let mut other_args = args;
+    other_args.account_number = 13;
+    let other_sign_doc = build_sign_doc(&parts, &other_args).unwrap();
+    assert!(!public_key
+        .verify_tx_signature(&other_sign_doc, &parts.signatures[0])
+        .unwrap());
+
+    let other_key = CosmosPrivateKey::from_secret(b"otherSecret")
+        .to_public_key("cosmospub")
+        .unwrap();
+    assert!(!other_key
+        .verify_tx_signature(&sign_doc, &parts.signatures[0])
+        .unwrap());
+
+    assert!(public_key
+        .verify_tx_signature(&sign_doc, &[0u8; 10])
+        .is_err());
+}
+
+#[test]
+fn test_sign_std_msg_deterministic() {
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use cosmos_sdk_proto::cosmos::base::v1beta1::Coin;
+
+    let key = CosmosPrivateKey::from_secret(b"mySecret");
+    let address = key.to_address("cosmos").unwrap().to_string();
+    let messages = || {
+        vec![
+            Msg::new(
+                "/cosmos.bank.v1beta1.MsgSend",
+                MsgSend {
+                    from_address: address.clone(),
+                    to_address: address.clone(),
+                    amount: vec![Coin {
+                        denom: "uatom".to_string(),
+                        amount: "1".to_string(),
+                    }],
+                },
+            ),
+            Msg::new(
+                "/cosmos.bank.v1beta1.MsgSend",
+                MsgSend {
+                    from_address: address.clone(),
+                    to_address: address.clone(),
+                    amount: vec![],
+                },
+            ),
+        ]
+    };
+    let args = MessageArgs {
+        sequence: 1,
+        fee: Fee {
+            amount: vec![crate::Coin::new(500u16.into(), "uatom".to_string())],
+            gas_limit: 200000,
+            payer: None,
+            granter: None,
+        },
+        account_number: 7,
+        ..test_message_args()
+    };
+
+    let first = key.sign_std_msg(&messages(), args.clone(), "memo").unwrap();
+    let second = key.sign_std_msg(&messages(), args, "memo").unwrap();
+    assert_eq!(first, second);
+}
+
+#[cfg(feature = "ethermint")]
+#[test]
+fn test_ethermint_low_s() {
+    let half_n = Uint256::from_be_bytes(&CurveN) / 2u8.into();
+    let sk = EthermintPrivateKey::from_secret(b"mySecret");
+    for i in 0..64u8 {
+        let signature = sk.sign_bytes(&[i; 32]).unwrap();
+        assert_eq!(signature.len(), 65);
+        assert!(Uint256::from_be_bytes(&signature[32..64]) <= half_n);
+
+        // the malleated high S form of the same signature normalizes back to it
+        let mut high_s = signature.clone();
+        let s = Uint256::from_be_bytes(&signature[32..64]);
+        high_s[32..64].copy_from_slice(&(Uint256::from_be_bytes(&CurveN) - s).to_be_bytes());
+        high_s[64] = flip_recovery_id(signature[64]);
+        assert!(Uint256::from_be_bytes(&high_s[32..64]) > half_n);
+        normalize_recoverable_s(&mut high_s);
+        assert_eq!(high_s, signature);
+    }
+}
+
+#[test]
+fn test_sign_tipped() {
+    use crate::Coin;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    let tipper = CosmosPrivateKey::from_secret(b"tipper");
+    let fee_payer = CosmosPrivateKey::from_secret(b"feePayer");
+    let tipper_address = tipper.to_address("cosmos").unwrap();
+    let fee_payer_address = fee_payer.to_address("cosmos").unwrap();
+    let msg = Msg::new(
+        "/cosmos.bank.v1beta1.MsgSend",
+        MsgSend {
+            from_address: tipper_address.to_string(),
+            to_address: fee_payer_address.to_string(),
+            amount: vec![],
+        },
+    );
+    let args = |sequence, account_number, payer| MessageArgs {
+        sequence,
+        fee: Fee {
+            amount: vec![Coin::new(500u32.into(), "uatom".to_string())],
+            gas_limit: 200000,
+            payer,
+            granter: None,
+        },
+        account_number,
+        ..test_message_args()
+    };
+    let tip = Tip {
+        amount: vec![Coin::new(10u32.into(), "ufoo".to_string())],
+        tipper: Some(tipper_address),
+    };
+    let tipper_args = args(3, 7, None);
+    let fee_payer_args = args(1, 9, Some(fee_payer_address));
+
+    let tx = sign_tipped(
+        &[msg],
+        "",
+        tip.clone(),
+        &tipper,
+        &tipper_args,
+        &fee_payer,
+        &fee_payer_args,
+    )
+    .unwrap();
+    assert_eq!(tx.signatures.len(), 2);
+    let auth_info = AuthInfo::decode(tx.auth_info_bytes.as_slice()).unwrap();
+    assert_eq!(auth_info.tip, Some(tip.clone().into()));
+    let modes: Vec<_> = auth_info
+        .signer_infos
+        .iter()
+        .map(|s| match &s.mode_info.as_ref().unwrap().sum {
+            Some(mode_info::Sum::Single(single)) => single.mode,
+            _ => panic!("Expected single signer mode"),
+        })
+        .collect();
+    assert_eq!(
+        modes,
+        vec![SignMode::DirectAux as i32, SignMode::Direct as i32]
+    );
+
+    let aux_sign_doc = SignDocDirectAux {
+        body_bytes: tx.body_bytes.clone(),
+        public_key: Some(tipper.to_public_key_any().unwrap()),
+        chain_id: "chain-0".to_string(),
+        account_number: 7,
+        sequence: 3,
+        tip: Some(tip.into()),
+    };
+    assert!(tipper
+        .to_public_key("cosmospub")
+        .unwrap()
+        .verify_bytes(&aux_sign_doc.encode_to_vec(), &tx.signatures[0]));
+    let sign_doc = SignDoc {
+        body_bytes: tx.body_bytes.clone(),
+        auth_info_bytes: tx.auth_info_bytes.clone(),
+        chain_id: "chain-0".to_string(),
+        account_number: 9,
+    };
+    assert!(fee_payer
+        .to_public_key("cosmospub")
+        .unwrap()
+        .verify_bytes(&sign_doc.encode_to_vec(), &tx.signatures[1]));
+}
+
+#[test]
+fn test_sign_std_msg_amino() {
+    use crate::Coin;
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+    use serde_json::json;
+
+    let key = CosmosPrivateKey::from_secret(b"mySecret");
+    let address = key.to_address("cosmos").unwrap();
+    let msg = Msg::new(
+        "/cosmos.bank.v1beta1.MsgSend",
+        MsgSend {
+            from_address: address.to_string(),
+            to_address: address.to_string(),
+            amount: vec![Coin::new(1000u32.into(), "uatom".to_string()).into()],
+        },
+    );
+    let amino_msg = AminoMsg {
+        msg_type: "cosmos-sdk/MsgSend".to_string(),
+        value: json!({
+            "from_address": address.to_string(),
+            "to_address": address.to_string(),
+            "amount": [{"denom": "uatom", "amount": "1000"}]
+        }),
+    };
+    let args = MessageArgs {
+        sequence: 2,
+        fee: Fee {
+            amount: vec![Coin::new(500u32.into(), "uatom".to_string())],
+            gas_limit: 200000,
+            payer: None,
+            granter: None,
+        },
+        account_number: 7,
+        ..test_message_args()
+    };
+
+    let tx_bytes = key
+        .sign_std_msg_amino(&[msg.clone()], &[amino_msg.clone()], args.clone(), "memo")
+        .unwrap();
+    let tx = TxRaw::decode(tx_bytes.as_slice()).unwrap();
+    let auth_info = AuthInfo::decode(tx.auth_info_bytes.as_slice()).unwrap();
+    match &auth_info.signer_infos[0].mode_info.as_ref().unwrap().sum {
+        Some(mode_info::Sum::Single(single)) => {
+            assert_eq!(single.mode, SignMode::LegacyAminoJson as i32)
+        }
+        _ => panic!("Expected single signer mode"),
+    }
+    assert!(key.to_public_key("cosmospub").unwrap().verify_bytes(
+        &std_sign_doc_bytes(&[amino_msg.clone()], &args, "memo"),
+        &tx.signatures[0]
+    ));
+
+    assert!(key
+        .sign_std_msg_amino(&[msg.clone(), msg], &[amino_msg], args, "memo")
+        .is_err());
+}
+
+#[test]
+fn test_zero_key_errors() {
+    let key = CosmosPrivateKey([0; 32]);
+    let args = MessageArgs {
+        fee: Fee::default(),
+        ..test_message_args()
+    };
+    let msg = Msg::new(
+        "/cosmos.bank.v1beta1.MsgSend",
+        ProtoSecp256k1Pubkey::default(),
+    );
+
+    assert!(key.sign_bytes(b"hello").is_err());
+    assert!(key.to_address("cosmos").is_err());
+    assert!(key
+        .sign_std_msg(&[msg.clone()], args.clone(), "memo")
+        .is_err());
+    assert!(key
+        .get_signed_tx(&[msg.clone()], args.clone(), "memo")
+        .is_err());
+    assert!(sign_multi(&[msg], &[(key, args)], "memo").is_err());
+
+    #[cfg(feature = "ethermint")]
+    assert!(EthermintPrivateKey([0; 32]).sign_bytes(b"hello").is_err());
+}
@@ -147,6 +147,31 @@ impl PublicKey for CosmosPublicKey {
     }
 }
 
+impl CosmosPublicKey {
+    /// Verifies a compact secp256k1 signature over the sha256 digest of `msg`, as produced by
+    /// `PrivateKey::sign_bytes` for a CosmosPrivateKey. Returns false for any malformed signature
+    pub fn verify_bytes(&self, msg: &[u8], sig: &[u8]) -> bool {
+        self.verify_tx_signature(msg, sig).unwrap_or(false)
+    }
+
+    /// Checks that `signature` is a valid signature by this key over the protobuf encoded
+    /// SignDoc `sign_doc_bytes`, this lets a received TxRaw be checked against its claimed signer
+    /// before acting on it. Returns an error if the signature or this key are malformed
+    pub fn verify_tx_signature(
+        &self,
+        sign_doc_bytes: &[u8],
+        signature: &[u8],
+    ) -> Result<bool, PublicKeyError> {
+        let key = secp256k1::PublicKey::from_slice(&self.bytes)?;
+        let signature = secp256k1::ecdsa::Signature::from_compact(signature)?;
+        let digest = Sha256::digest(sign_doc_bytes);
+        let msg = secp256k1::Message::from_digest_slice(&digest)?;
+        Ok(secp256k1::SECP256K1
+            .verify_ecdsa(&msg, &signature, &key)
+            .is_ok())
+    }
+}
+
 /// Represents a public key of an Ethereum private key in the Cosmos Network under Ethermint.
 #[cfg(feature = "ethermint")]
 #[derive(PartialEq, Eq, Copy, Clone, Hash)]
@@ -353,6 +378,24 @@ fn parse_base64_pubkey() {
     let _key: CosmosPublicKey = key.parse().unwrap();
 }
 
+#[test]
+fn test_verify_bytes() {
+    use crate::private_key::{CosmosPrivateKey, PrivateKey};
+    let private_key = CosmosPrivateKey::from_secret("mySecret".as_bytes());
+    let public_key = private_key.to_public_key("cosmospub").unwrap();
+    let msg = b"Login to example.com at 1700000000";
+    let sig = private_key.sign_bytes(msg).unwrap();
+    assert_eq!(sig.len(), 64);
+    assert!(public_key.verify_bytes(msg, &sig));
+    assert!(!public_key.verify_bytes(b"Login to example.com at 1700000001", &sig));
+    assert!(!public_key.verify_bytes(msg, &sig[1..]));
+
+    let other = CosmosPrivateKey::from_secret("otherSecret".as_bytes())
+        .to_public_key("cosmospub")
+        .unwrap();
+    assert!(!other.verify_bytes(msg, &sig));
+}
+
 #[test]
 fn test_default_prefix() {
     CosmosPublicKey::from_bytes([0; 33], CosmosPublicKey::DEFAULT_PREFIX).unwrap();
@@ -0,0 +1,70 @@
+//! Decoding of signed transactions, useful for logging or displaying a transaction before it is broadcast
+
+use crate::error::CosmosGrpcError;
+use cosmos_sdk_proto::cosmos::tx::v1beta1::{AuthInfo, Tx, TxBody, TxRaw};
+use prost::Message;
+
+/// Decodes the bytes of a signed TxRaw, such as those produced by `PrivateKey::sign_std_msg`,
+/// back into a Tx with its body and auth info decoded
+pub fn decode_tx(tx_bytes: &[u8]) -> Result<Tx, CosmosGrpcError> {
+    let raw = TxRaw::decode(tx_bytes)?;
+    let body = TxBody::decode(raw.body_bytes.as_slice())?;
+    let auth_info = AuthInfo::decode(raw.auth_info_bytes.as_slice())?;
+    Ok(Tx {
+        body: Some(body),
+        auth_info: Some(auth_info),
+        signatures: raw.signatures,
+    })
+}
+
+/// Lists the type url of each message in the provided Tx, in order, for example
+/// "/cosmos.bank.v1beta1.MsgSend"
+pub fn describe_tx(tx: &Tx) -> Vec<String> {
+    match &tx.body {
+        Some(body) => body.messages.iter().map(|m| m.type_url.clone()).collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::type_urls::MSG_SEND_TYPE_URL;
+    use crate::private_key::test_message_args;
+    use crate::{CosmosPrivateKey, MessageArgs, Msg, PrivateKey};
+    use cosmos_sdk_proto::cosmos::bank::v1beta1::MsgSend;
+
+    #[test]
+    fn test_decode_tx() {
+        let key = CosmosPrivateKey::from_secret(b"mySecret");
+        let address = key.to_address("cosmos").unwrap().to_string();
+        let msg = Msg::new(
+            MSG_SEND_TYPE_URL,
+            MsgSend {
+                from_address: address.clone(),
+                to_address: address,
+                amount: vec![],
+            },
+        );
+        let args = MessageArgs {
+            sequence: 4,
+            ..test_message_args()
+        };
+        let tx_bytes = key
+            .sign_std_msg(&[msg.clone(), msg], args, "a memo")
+            .unwrap();
+
+        let tx = decode_tx(&tx_bytes).unwrap();
+        let body = tx.body.as_ref().unwrap();
+        assert_eq!(body.memo, "a memo");
+        assert_eq!(body.timeout_height, 100);
+        assert_eq!(tx.auth_info.as_ref().unwrap().signer_infos[0].sequence, 4);
+        assert_eq!(tx.signatures.len(), 1);
+        assert_eq!(
+            describe_tx(&tx),
+            vec![MSG_SEND_TYPE_URL.to_string(), MSG_SEND_TYPE_URL.to_string()]
+        );
+
+        assert!(decode_tx(&[0xff, 0xff, 0xff]).is_err());
+    }
+}
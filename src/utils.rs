@@ -1,9 +1,11 @@
 use crate::error::{ArrayStringError, ByteDecodeError, CosmosGrpcError, SdkErrorCode};
 use crate::Coin;
+use base64::{engine::general_purpose, Engine as _};
 use bytes::BytesMut;
 use cosmos_sdk_proto::cosmos::base::abci::v1beta1::TxResponse;
-use prost::{DecodeError, Message};
+use prost::{DecodeError, EncodeError, Message};
 use prost_types::Any;
+use sha2::{Digest, Sha256};
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::fmt::Result as FmtResult;
@@ -51,6 +53,12 @@ pub fn bytes_to_hex_str(bytes: &[u8]) -> String {
         .fold(String::new(), |acc, x| acc + &x)
 }
 
+/// Computes the hash of a transaction the same way the node does, the sha256 of the
+/// raw transaction bytes as included in a block, encoded as upper case hex
+pub fn tx_hash(tx_bytes: &[u8]) -> String {
+    bytes_to_hex_str(&Sha256::digest(tx_bytes)).to_uppercase()
+}
+
 #[derive(PartialEq, Eq, Copy, Clone, Hash, Deserialize, Serialize)]
 pub struct ArrayString {
     chars: [Option<char>; ArrayString::MAX_LEN],
@@ -152,6 +160,42 @@ pub fn determine_min_fees_and_gas(input: &TxResponse) -> Option<FeeInfo> {
     }
 }
 
+/// Typed accessors for the result of a transaction, implemented for both the raw TxResponse
+/// and the TransactionResponse returned by the transaction sending methods of Contact
+pub trait TxResponseExt {
+    /// Returns true if the transaction executed successfully, that is its code is zero
+    fn succeeded(&self) -> bool;
+
+    /// Returns the error the transaction failed with, if it failed with an error from the
+    /// sdk codespace. Module specific errors are not covered and return None
+    fn sdk_error_code(&self) -> Option<SdkErrorCode>;
+
+    /// Returns the fraction of the gas limit actually used, a value well below one means
+    /// the gas limit and therefore the fee could have been lower. Zero if no gas was wanted
+    fn gas_efficiency(&self) -> f64;
+}
+
+impl TxResponseExt for TxResponse {
+    fn succeeded(&self) -> bool {
+        self.code == 0
+    }
+
+    fn sdk_error_code(&self) -> Option<SdkErrorCode> {
+        if self.codespace == "sdk" {
+            SdkErrorCode::from_code(self.code)
+        } else {
+            None
+        }
+    }
+
+    fn gas_efficiency(&self) -> f64 {
+        if self.gas_wanted == 0 {
+            return 0.0;
+        }
+        self.gas_used as f64 / self.gas_wanted as f64
+    }
+}
+
 /// Checks a tx response code for known issues returns true if tx is good, false if the tx
 /// has some known error
 pub fn check_for_sdk_error(input: &TxResponse) -> Result<(), CosmosGrpcError> {
@@ -162,20 +206,20 @@ pub fn check_for_sdk_error(input: &TxResponse) -> Result<(), CosmosGrpcError> {
 
     // check for known errors in the sdk codespace, if the error is module
     // specific we will not detect it and the error will go un-noticed
-    if input.codespace == "sdk" {
-        if let Some(e) = SdkErrorCode::from_code(input.code) {
-            return Err(CosmosGrpcError::TransactionFailed {
-                tx: input.clone(),
-                time: Duration::from_secs(0),
-                sdk_error: Some(e),
-            });
-        }
+    if let Some(e) = input.sdk_error_code() {
+        return Err(CosmosGrpcError::TransactionFailed {
+            tx: input.clone(),
+            time: Duration::from_secs(0),
+            sdk_error: Some(e),
+        });
     }
 
     Ok(())
 }
 
-/// Helper function for encoding the the proto any type
+/// Helper function for encoding the the proto any type, packs the protobuf encoding of
+/// `input` into an Any with the given `type_url`, for example to build proposal content.
+/// Panics if encoding fails, see encode_any_checked for a version that returns an error
 pub fn encode_any(input: impl prost::Message, type_url: impl Into<String>) -> Any {
     let mut value = Vec::new();
     input.encode(&mut value).unwrap();
@@ -185,12 +229,41 @@ pub fn encode_any(input: impl prost::Message, type_url: impl Into<String>) -> An
     }
 }
 
+/// The same as encode_any but returns an error rather than panicking if encoding fails
+pub fn encode_any_checked(
+    input: impl prost::Message,
+    type_url: impl Into<String>,
+) -> Result<Any, EncodeError> {
+    let mut value = Vec::new();
+    input.encode(&mut value)?;
+    Ok(Any {
+        type_url: type_url.into(),
+        value,
+    })
+}
+
 pub fn decode_any<T: Message + Default>(any: Any) -> Result<T, DecodeError> {
     let bytes = any.value;
 
     decode_bytes(bytes)
 }
 
+/// Decodes the value of `any` as a `T` after checking that its type url is `type_url`, for
+/// example to read the content of a governance proposal. Unlike decode_any this returns an
+/// error rather than silently decoding a different message type with a compatible encoding
+pub fn decode_any_checked<T: Message + Default>(
+    any: &Any,
+    type_url: &str,
+) -> Result<T, CosmosGrpcError> {
+    if any.type_url != type_url {
+        return Err(CosmosGrpcError::BadInput(format!(
+            "Expected type url {} got {}",
+            type_url, any.type_url
+        )));
+    }
+    Ok(T::decode(any.value.as_slice())?)
+}
+
 pub fn decode_bytes<T: Message + Default>(bytes: Vec<u8>) -> Result<T, DecodeError> {
     let mut buf = BytesMut::with_capacity(bytes.len());
     buf.extend_from_slice(&bytes);
@@ -200,11 +273,243 @@ pub fn decode_bytes<T: Message + Default>(bytes: Vec<u8>) -> Result<T, DecodeErr
     T::decode(buf)
 }
 
+/// Finds the value of the attribute `attr_key` of the first event of type `event_type` emitted
+/// by a transaction, for example the `proposal_id` of a `submit_proposal` event. See
+/// find_all_event_attributes for details
+pub fn find_event_attribute(tx: &TxResponse, event_type: &str, attr_key: &str) -> Option<String> {
+    find_all_event_attributes(tx, event_type, attr_key)
+        .into_iter()
+        .next()
+}
+
+/// Finds the values of the attribute `attr_key` of every event of type `event_type` emitted by
+/// a transaction, in the order they were emitted. The top level events are used if the node
+/// populates them, otherwise this falls back to the legacy per message logs, which are only
+/// populated by Cosmos SDK versions before 0.50. Some versions base64 encode the attributes
+/// of the top level events, these are decoded as needed
+pub fn find_all_event_attributes(tx: &TxResponse, event_type: &str, attr_key: &str) -> Vec<String> {
+    tx_events(tx)
+        .iter()
+        .filter(|e| e.event_type == event_type)
+        .flat_map(|e| e.get_all(attr_key))
+        .collect()
+}
+
+/// An event emitted by a transaction, read from either the top level events or the legacy logs
+pub(crate) struct TxEvent {
+    pub event_type: String,
+    attributes: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl TxEvent {
+    /// Returns the value of the first attribute of this event with the key `attr_key`
+    pub fn get(&self, attr_key: &str) -> Option<String> {
+        self.get_all(attr_key).into_iter().next()
+    }
+
+    fn get_all(&self, attr_key: &str) -> Vec<String> {
+        self.attributes
+            .iter()
+            .filter_map(|(key, value)| decode_event_attribute(key, value, attr_key))
+            .collect()
+    }
+}
+
+/// Returns the events emitted by a transaction in order, see find_all_event_attributes
+pub(crate) fn tx_events(tx: &TxResponse) -> Vec<TxEvent> {
+    if !tx.events.is_empty() {
+        tx.events
+            .iter()
+            .map(|e| TxEvent {
+                event_type: e.r#type.clone(),
+                attributes: e
+                    .attributes
+                    .iter()
+                    .map(|a| (attribute_bytes(&a.key), attribute_bytes(&a.value)))
+                    .collect(),
+            })
+            .collect()
+    } else {
+        tx.logs
+            .iter()
+            .flat_map(|log| log.events.iter())
+            .map(|e| TxEvent {
+                event_type: e.r#type.clone(),
+                attributes: e
+                    .attributes
+                    .iter()
+                    .map(|a| (a.key.as_bytes().to_vec(), a.value.as_bytes().to_vec()))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Event attributes are bytes in some Tendermint versions and strings in others, so both
+/// are accepted
+fn attribute_bytes<T: AsRef<[u8]>>(value: &T) -> Vec<u8> {
+    value.as_ref().to_vec()
+}
+
+/// Returns the value of an event attribute if its key is `attr_key`, either as is or base64
+/// encoded, in which case the value is decoded as well
+fn decode_event_attribute(key: &[u8], value: &[u8], attr_key: &str) -> Option<String> {
+    if key == attr_key.as_bytes() {
+        return Some(String::from_utf8_lossy(value).to_string());
+    }
+    match general_purpose::STANDARD.decode(key) {
+        Ok(decoded) if decoded == attr_key.as_bytes() => {
+            let value = general_purpose::STANDARD
+                .decode(value)
+                .unwrap_or_else(|_| value.to_vec());
+            Some(String::from_utf8_lossy(&value).to_string())
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     // Note this useful idiom: importing names from outer (for mod tests) scope.
     use super::*;
 
+    #[test]
+    fn test_decode_any_checked() {
+        use crate::client::type_urls::{
+            PARAMETER_CHANGE_PROPOSAL_TYPE_URL, SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL,
+        };
+        use cosmos_sdk_proto::cosmos::params::v1beta1::{ParamChange, ParameterChangeProposal};
+        use cosmos_sdk_proto::cosmos::upgrade::v1beta1::SoftwareUpgradeProposal;
+
+        let proposal = ParameterChangeProposal {
+            title: "title".to_string(),
+            description: "description".to_string(),
+            changes: vec![ParamChange {
+                subspace: "staking".to_string(),
+                key: "MaxValidators".to_string(),
+                value: "200".to_string(),
+            }],
+        };
+        let any = encode_any(proposal.clone(), PARAMETER_CHANGE_PROPOSAL_TYPE_URL);
+        let decoded: ParameterChangeProposal =
+            decode_any_checked(&any, PARAMETER_CHANGE_PROPOSAL_TYPE_URL).unwrap();
+        assert_eq!(decoded, proposal);
+        assert_eq!(
+            encode_any_checked(proposal.clone(), PARAMETER_CHANGE_PROPOSAL_TYPE_URL).unwrap(),
+            any
+        );
+
+        assert!(matches!(
+            decode_any_checked::<SoftwareUpgradeProposal>(&any, SOFTWARE_UPGRADE_PROPOSAL_TYPE_URL),
+            Err(CosmosGrpcError::BadInput(_))
+        ));
+        let garbage = Any {
+            type_url: PARAMETER_CHANGE_PROPOSAL_TYPE_URL.to_string(),
+            value: vec![0xff, 0xff],
+        };
+        assert!(matches!(
+            decode_any_checked::<ParameterChangeProposal>(
+                &garbage,
+                PARAMETER_CHANGE_PROPOSAL_TYPE_URL
+            ),
+            Err(CosmosGrpcError::DecodeError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_find_event_attribute() {
+        use cosmos_sdk_proto::cosmos::base::abci::v1beta1::{
+            AbciMessageLog, Attribute, StringEvent,
+        };
+        use cosmos_sdk_proto::tendermint::abci::{Event, EventAttribute};
+
+        let legacy = TxResponse {
+            logs: vec![AbciMessageLog {
+                msg_index: 0,
+                log: String::new(),
+                events: vec![StringEvent {
+                    r#type: "submit_proposal".to_string(),
+                    attributes: vec![Attribute {
+                        key: "proposal_id".to_string(),
+                        value: "7".to_string(),
+                    }],
+                }],
+            }],
+            ..Default::default()
+        };
+        assert_eq!(
+            find_event_attribute(&legacy, "submit_proposal", "proposal_id"),
+            Some("7".to_string())
+        );
+        assert_eq!(
+            find_event_attribute(&legacy, "transfer", "proposal_id"),
+            None
+        );
+
+        let attribute = |key: &str, value: &str| EventAttribute {
+            key: key.into(),
+            value: value.into(),
+            index: true,
+        };
+        let events = TxResponse {
+            events: vec![
+                Event {
+                    r#type: "transfer".to_string(),
+                    attributes: vec![attribute("amount", "1stake")],
+                },
+                Event {
+                    r#type: "transfer".to_string(),
+                    // base64 of "amount" and "2stake"
+                    attributes: vec![attribute("YW1vdW50", "MnN0YWtl")],
+                },
+            ],
+            ..legacy
+        };
+        assert_eq!(
+            find_all_event_attributes(&events, "transfer", "amount"),
+            vec!["1stake".to_string(), "2stake".to_string()]
+        );
+        assert!(find_all_event_attributes(&events, "submit_proposal", "proposal_id").is_empty());
+    }
+
+    #[test]
+    fn test_tx_response_ext() {
+        let tx = TxResponse {
+            gas_wanted: 200,
+            gas_used: 150,
+            ..Default::default()
+        };
+        assert!(tx.succeeded());
+        assert_eq!(tx.sdk_error_code(), None);
+        assert_eq!(tx.gas_efficiency(), 0.75);
+
+        let tx = TxResponse {
+            code: 5,
+            codespace: "sdk".to_string(),
+            ..tx
+        };
+        assert!(!tx.succeeded());
+        assert_eq!(
+            tx.sdk_error_code(),
+            Some(SdkErrorCode::ErrInsufficientFunds)
+        );
+        // the same code in a module codespace is a different error
+        let tx = TxResponse {
+            codespace: "bank".to_string(),
+            ..tx
+        };
+        assert_eq!(tx.sdk_error_code(), None);
+        assert_eq!(TxResponse::default().gas_efficiency(), 0.0);
+    }
+
+    #[test]
+    fn test_tx_hash() {
+        assert_eq!(
+            tx_hash(&[]),
+            "E3B0C44298FC1C149AFBF4C8996FB92427AE41E4649B934CA495991B7852B855"
+        );
+    }
+
     #[test]
     fn test_determine_fees() {
         let below_min_fees_tx_response = TxResponse {